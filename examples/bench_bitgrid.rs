@@ -0,0 +1,84 @@
+// Compares a BitGrid with a World on the benchmark soup from rust_conway::pattern::benchmark: the
+// memory each keeps per cell, and the fastest of a few runs of ticks on a bounded and a toroidal
+// grid. The two are checked against each other afterwards, so a faster BitGrid is also a right one.
+//
+//     cargo run --release --features bitgrid --example bench_bitgrid -- [SIZE] [GENERATIONS]
+
+use std::env;
+use std::mem::size_of;
+use std::process;
+use std::time::{Duration, Instant};
+
+use rust_conway::bitgrid::BitGrid;
+use rust_conway::{pattern, Cell, Topology, Vector, World};
+
+const DEFAULT_SIZE: i32 = 2000;
+const DEFAULT_GENERATIONS: u32 = 20;
+const RUNS: u32 = 3;
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let side = args.next().map_or(DEFAULT_SIZE, |value| value.parse().expect("SIZE must be a number"));
+    let generations = args.next().map_or(DEFAULT_GENERATIONS, |value| value.parse().expect("GENERATIONS must be a number"));
+
+    let size = Vector { x: side, y: side };
+    let soup = pattern::benchmark(&size);
+    let cells = side as usize * side as usize;
+
+    // A World holds each cell with its coordinate, and a neighbour sum and an edge sum beside it.
+    let world_bytes = cells * (size_of::<Cell>() + 2 * size_of::<u32>());
+    let grid_bytes = BitGrid::new(&size, Topology::Bounded, "B3/S23".parse().unwrap()).memory_bytes();
+
+    println!(
+        "{}x{}: World {:.1} MB, BitGrid {:.1} KB ({:.0}x smaller)",
+        side,
+        side,
+        world_bytes as f64 / (1 << 20) as f64,
+        grid_bytes as f64 / (1 << 10) as f64,
+        world_bytes as f64 / grid_bytes as f64
+    );
+
+    for topology in [Topology::Bounded, Topology::Torus] {
+        let mut world_time = Duration::MAX;
+        let mut grid_time = Duration::MAX;
+
+        for _ in 0..RUNS {
+            let mut world = World::from_pattern(&soup, &size);
+            world.set_topology(topology);
+            let mut grid = BitGrid::from_world(&world).expect("the default world suits a BitGrid");
+
+            let started = Instant::now();
+
+            for _ in 0..generations {
+                world.tick();
+            }
+
+            world_time = world_time.min(started.elapsed());
+
+            let started = Instant::now();
+
+            for _ in 0..generations {
+                grid.tick();
+            }
+
+            grid_time = grid_time.min(started.elapsed());
+
+            let matches = (0..side as usize).all(|x| (0..side as usize).all(|y| grid.get(x, y) == world.cells[x][y].is_alive()));
+
+            if !matches {
+                eprintln!("BitGrid and World disagree after {} generations on a {}", generations, topology.name());
+                process::exit(1);
+            }
+        }
+
+        let per_tick = |elapsed: Duration| elapsed.as_secs_f64() * 1000.0 / generations.max(1) as f64;
+
+        println!(
+            "{}: World {:.2}ms per tick, BitGrid {:.2}ms per tick ({:.1}x)",
+            topology.name(),
+            per_tick(world_time),
+            per_tick(grid_time),
+            world_time.as_secs_f64() / grid_time.as_secs_f64().max(f64::EPSILON)
+        );
+    }
+}
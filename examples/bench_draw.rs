@@ -0,0 +1,74 @@
+// Times drawing the benchmark soup from rust_conway::pattern::benchmark as terminal text, plain
+// and textured, against building the same text with a format! for every cell, the way frames
+// were once drawn. The fastest of a few runs of many frames is reported for each.
+//
+//     cargo run --release --example bench_draw -- [SIZE] [FRAMES]
+
+use std::env;
+use std::time::{Duration, Instant};
+
+use ratatui::text::{Line, Text};
+use rust_conway::{pattern, Vector, World};
+
+const DEFAULT_SIZE: i32 = 200;
+const DEFAULT_FRAMES: u32 = 200;
+const RUNS: u32 = 5;
+
+type Draw = fn(&World) -> Text<'static>;
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let side = args.next().map_or(DEFAULT_SIZE, |value| value.parse().expect("SIZE must be a number"));
+    let frames = args.next().map_or(DEFAULT_FRAMES, |value| value.parse().expect("FRAMES must be a number"));
+
+    let size = Vector { x: side, y: side };
+    let world = World::from_pattern(&pattern::benchmark(&size), &size);
+
+    let drawers: [(&str, Draw); 3] = [
+        ("format! per cell", formatted),
+        ("draw_world", |world| world.draw_world(false)),
+        ("draw_world textured", |world| world.draw_world(true)),
+    ];
+
+    for (name, draw) in drawers {
+        let fastest = (0..RUNS)
+            .map(|_| {
+                let started = Instant::now();
+
+                for _ in 0..frames {
+                    std::hint::black_box(draw(&world));
+                }
+
+                started.elapsed()
+            })
+            .min()
+            .unwrap_or(Duration::ZERO);
+
+        println!(
+            "{}x{} {}: {} frames in {:.1}ms, {:.1}µs per frame",
+            side,
+            side,
+            name,
+            frames,
+            fastest.as_secs_f64() * 1000.0,
+            fastest.as_secs_f64() * 1_000_000.0 / frames.max(1) as f64
+        );
+    }
+}
+
+// Plain text with a fresh String for every cell's glyph.
+fn formatted(world: &World) -> Text<'static> {
+    let lines: Vec<Line> = (0..world.size.y as usize)
+        .map(|y| {
+            let mut line = String::new();
+
+            for x in 0..world.size.x as usize {
+                line.push_str(&format!("{}", if world.cells[x][y].is_alive() { '#' } else { ' ' }));
+            }
+
+            Line::from(line)
+        })
+        .collect();
+
+    Text::from(lines)
+}
@@ -0,0 +1,110 @@
+// Times World::tick against a tick that recounts every cell's neighbours from scratch, walking
+// the whole square around it and skipping the cells its neighbourhood leaves out, the way ticks
+// worked before the offsets were precomputed. Both run on the benchmark soup on a bounded world,
+// and their grids are compared after every generation, so the faster one is also shown to agree.
+//
+//     cargo run --release --example bench_neighbours -- [SIZE] [GENERATIONS]
+
+use std::env;
+use std::process;
+use std::time::{Duration, Instant};
+
+use rust_conway::{pattern, Neighbourhood, Vector, World};
+
+const DEFAULT_SIZE: i32 = 200;
+const DEFAULT_GENERATIONS: u32 = 100;
+const RUNS: u32 = 3;
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let side = args.next().map_or(DEFAULT_SIZE, |value| value.parse().expect("SIZE must be a number"));
+    let generations = args.next().map_or(DEFAULT_GENERATIONS, |value| value.parse().expect("GENERATIONS must be a number"));
+
+    let size = Vector { x: side, y: side };
+    let soup = pattern::benchmark(&size);
+
+    for (neighbourhood, radius) in [(Neighbourhood::Moore, 1), (Neighbourhood::Moore, 2), (Neighbourhood::VonNeumann, 2)] {
+        let world = || {
+            let mut world = World::from_pattern(&soup, &size);
+            world.set_neighbourhood(neighbourhood);
+            world.set_radius(radius);
+            world
+        };
+
+        let mut recounted = Duration::MAX;
+        let mut ticked = Duration::MAX;
+
+        for _ in 0..RUNS {
+            let mut reference = world();
+            let mut grid = reference.snapshot();
+            let started = Instant::now();
+
+            for _ in 0..generations {
+                grid = recounted_tick(&grid, &reference);
+            }
+
+            recounted = recounted.min(started.elapsed());
+
+            let started = Instant::now();
+
+            for _ in 0..generations {
+                reference.tick();
+            }
+
+            ticked = ticked.min(started.elapsed());
+
+            if reference.snapshot() != grid {
+                eprintln!("World::tick and the recount disagree after {} generations", generations);
+                process::exit(1);
+            }
+        }
+
+        let per_tick = |elapsed: Duration| elapsed.as_secs_f64() * 1_000_000.0 / generations.max(1) as f64;
+
+        println!(
+            "{}x{} {} r{}: recounting {:.1}µs per tick, World::tick {:.1}µs per tick ({:.1}x)",
+            side,
+            side,
+            neighbourhood.name(),
+            radius,
+            per_tick(recounted),
+            per_tick(ticked),
+            recounted.as_secs_f64() / ticked.as_secs_f64().max(f64::EPSILON)
+        );
+    }
+}
+
+// Indexed [x][y] like World::cells. Only `world`'s settings are read, never its cells.
+fn recounted_tick(grid: &[Vec<bool>], world: &World) -> Vec<Vec<bool>> {
+    let radius = world.radius;
+
+    (0..world.size.x)
+        .map(|x| {
+            (0..world.size.y)
+                .map(|y| {
+                    let mut sum = 0;
+
+                    for dx in -radius..=radius {
+                        for dy in -radius..=radius {
+                            if !world.neighbourhood.includes(dx, dy, radius, y % 2 == 1) {
+                                continue;
+                            }
+
+                            let neighbour = Vector { x: x + dx, y: y + dy };
+
+                            if neighbour.out_of_bounds(&Vector { x: 0, y: 0 }, &world.size) {
+                                continue;
+                            }
+
+                            if grid[neighbour.x as usize][neighbour.y as usize] {
+                                sum += world.weights.get(dx, dy);
+                            }
+                        }
+                    }
+
+                    world.rule.next_state(grid[x as usize][y as usize], sum)
+                })
+                .collect()
+        })
+        .collect()
+}
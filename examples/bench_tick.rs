@@ -0,0 +1,52 @@
+// Times ticks of the benchmark soup from rust_conway::pattern::benchmark, so engine changes can
+// be compared on the same work from commit to commit. Each world is run a few times over and
+// the fastest run is reported, which is the one least disturbed by whatever else was running.
+//
+//     cargo run --release --example bench_tick -- [SIZE] [GENERATIONS]
+
+use std::env;
+use std::time::{Duration, Instant};
+
+use rust_conway::pattern;
+use rust_conway::{Topology, Vector, World};
+
+const DEFAULT_SIZE: i32 = 256;
+const DEFAULT_GENERATIONS: u32 = 200;
+const RUNS: u32 = 5;
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let side = args.next().map_or(DEFAULT_SIZE, |value| value.parse().expect("SIZE must be a number"));
+    let generations = args.next().map_or(DEFAULT_GENERATIONS, |value| value.parse().expect("GENERATIONS must be a number"));
+
+    let size = Vector { x: side, y: side };
+    let soup = pattern::benchmark(&size);
+
+    for topology in [Topology::Bounded, Topology::Torus] {
+        let fastest = (0..RUNS)
+            .map(|_| {
+                let mut world = World::from_pattern(&soup, &size);
+                world.set_topology(topology);
+
+                let started = Instant::now();
+
+                for _ in 0..generations {
+                    world.tick();
+                }
+
+                started.elapsed()
+            })
+            .min()
+            .unwrap_or(Duration::ZERO);
+
+        println!(
+            "{}x{} {}: {} generations in {:.1}ms, {:.1}µs per tick",
+            side,
+            side,
+            topology.name(),
+            generations,
+            fastest.as_secs_f64() * 1000.0,
+            fastest.as_secs_f64() * 1_000_000.0 / generations.max(1) as f64
+        );
+    }
+}
@@ -0,0 +1,90 @@
+// Feeds random and mutated input to every pattern loader, failing if any of them panics instead
+// of returning an error. Mutations start from the valid patterns in fuzz/corpus.
+//
+//     cargo run --release --example fuzz_loaders -- [ITERATIONS] [SEED]
+
+use std::{env, fs, panic, process};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+include!(concat!(env!("CARGO_MANIFEST_DIR"), "/fuzz/load_everything.rs"));
+
+const CORPUS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fuzz/corpus");
+const DEFAULT_ITERATIONS: u64 = 100_000;
+// Characters that mean something to at least one of the formats, plus a multi-byte one.
+const ALPHABET: &[char] = &[
+    'x', 'y', '=', ',', ' ', '\n', '$', '!', '#', 'N', 'C', 'b', 'o', '.', 'O', '*', '0', '1', '2', '9',
+    '-', 'B', 'S', '/', 'R', 'M', 'é',
+];
+const MAX_MUTATIONS: usize = 4;
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let iterations = args.next().map_or(DEFAULT_ITERATIONS, |value| value.parse().expect("ITERATIONS must be a number"));
+    let seed = args.next().map_or(0, |value| value.parse().expect("SEED must be a number"));
+
+    let corpus: Vec<String> = fs::read_dir(CORPUS_DIR)
+        .expect("Failed to read the corpus")
+        .map(|entry| fs::read_to_string(entry.expect("Failed to read the corpus").path()).expect("Failed to read a corpus file"))
+        .collect();
+
+    // Every valid pattern must load before mutating it means anything.
+    for text in &corpus {
+        if let Err(error) = Pattern::parse(text, None) {
+            eprintln!("Corpus pattern failed to load: {}\n{}", error, text);
+            process::exit(1);
+        }
+    }
+
+    // Panics are caught and reported below, rather than printed as they happen.
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut failures = 0;
+
+    for iteration in 0..iterations {
+        let input = match iteration % 3 {
+            0 => random_text(&mut rng),
+            1 => String::from_utf8_lossy(&(0..rng.gen_range(0..64)).map(|_| rng.gen()).collect::<Vec<u8>>()).into_owned(),
+            _ => mutated(&corpus[rng.gen_range(0..corpus.len())], &mut rng),
+        };
+
+        let attempt = input.clone();
+
+        if panic::catch_unwind(move || load_everything(&attempt)).is_err() {
+            failures += 1;
+            println!("Panicked on {:?}", input);
+        }
+    }
+
+    println!("{} iterations from seed {}, {} panics", iterations, seed, failures);
+
+    if failures > 0 {
+        process::exit(1);
+    }
+}
+
+fn random_text(rng: &mut StdRng) -> String {
+    (0..rng.gen_range(0..64)).map(|_| ALPHABET[rng.gen_range(0..ALPHABET.len())]).collect()
+}
+
+// Replaces, removes or inserts a few characters.
+fn mutated(text: &str, rng: &mut StdRng) -> String {
+    let mut chars: Vec<char> = text.chars().collect();
+
+    for _ in 0..rng.gen_range(1..=MAX_MUTATIONS) {
+        let at = rng.gen_range(0..=chars.len());
+        let replacement = ALPHABET[rng.gen_range(0..ALPHABET.len())];
+
+        match rng.gen_range(0..3) {
+            0 if at < chars.len() => chars[at] = replacement,
+            1 if at < chars.len() => {
+                chars.remove(at);
+            }
+            _ => chars.insert(at, replacement),
+        }
+    }
+
+    chars.into_iter().collect()
+}
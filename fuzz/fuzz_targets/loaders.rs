@@ -0,0 +1,19 @@
+// libFuzzer's take on examples/fuzz_loaders.rs, guided by coverage rather than drawing input at
+// random. Start it from the same corpus, keeping what it finds out of the committed one:
+//
+//     cargo +nightly fuzz run loaders fuzz/artifacts/corpus fuzz/corpus
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+include!("../load_everything.rs");
+
+fuzz_target!(|data: &[u8]| {
+    // Bytes first, before the loaders that only take text.
+    let _ = World::load_bin(data);
+
+    if let Ok(input) = std::str::from_utf8(data) {
+        load_everything(input);
+    }
+});
@@ -0,0 +1,28 @@
+// Runs one input through every loader, shared by examples/fuzz_loaders.rs and the cargo-fuzz
+// target in fuzz_targets. The pattern readers cap the sizes they accept, so nothing here filters
+// what it builds.
+
+use rust_conway::pattern::{Format, Pattern};
+use rust_conway::rule::{LargerThanLife, Rule};
+use rust_conway::{Symmetry, Vector, World};
+
+fn load_everything(input: &str) {
+    for format in [None, Some(Format::Rle), Some(Format::Plaintext), Some(Format::Csv)] {
+        if let Ok(pattern) = Pattern::parse(input, format) {
+            let size = Vector { x: pattern.size.x.max(1), y: pattern.size.y.max(1) };
+            let world = World::from_pattern(&pattern, &size);
+            world.to_rle();
+
+            // A round trip that comes back different panics too, so it's reported like one.
+            let saved = world.save_bin();
+            assert_eq!(World::load_bin(&saved).expect("Saved world failed to load").save_bin(), saved);
+        }
+    }
+
+    let _ = World::from_rle(input, 1, None);
+    let _ = World::from_csv(input);
+    let _ = World::load_bin(input.as_bytes());
+    let _ = input.parse::<Rule>();
+    let _ = input.parse::<LargerThanLife>();
+    let _ = input.parse::<Symmetry>();
+}
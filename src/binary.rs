@@ -0,0 +1,238 @@
+// A compact binary save of the grid, its frame and its rule, for worlds too big to keep as text.
+// Everything is little-endian:
+//
+//     magic     4 bytes, "CWAY"
+//     version   1 byte
+//     width     u32
+//     height    u32
+//     frame     u64
+//     birth     u16 count of ranges, then each range's first and last count as u32s
+//     survival  the same
+//     grid      one bit per cell, row by row from the top left, lowest bit first
+//
+// Readers refuse versions newer than their own, and anything after the grid.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+use crate::error::ConwayError;
+use crate::rule::Rule;
+use crate::{Vector, World};
+
+const MAGIC: &[u8; 4] = b"CWAY";
+pub const VERSION: u8 = 1;
+
+impl World {
+    pub fn save_bin(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(VERSION);
+        bytes.extend((self.size.x as u32).to_le_bytes());
+        bytes.extend((self.size.y as u32).to_le_bytes());
+        bytes.extend(self.frames.to_le_bytes());
+
+        for ranges in [&self.rule.birth, &self.rule.survival] {
+            bytes.extend((ranges.len() as u16).to_le_bytes());
+
+            for range in ranges {
+                bytes.extend(range.start().to_le_bytes());
+                bytes.extend(range.end().to_le_bytes());
+            }
+        }
+
+        let mut grid = vec![0u8; (self.size.x as usize * self.size.y as usize).div_ceil(8)];
+
+        for y in 0..self.size.y as usize {
+            for x in 0..self.size.x as usize {
+                if self.cells[x][y].is_alive() {
+                    let bit = y * self.size.x as usize + x;
+                    grid[bit / 8] |= 1 << (bit % 8);
+                }
+            }
+        }
+
+        bytes.extend(grid);
+        bytes
+    }
+
+    pub fn load_bin(bytes: &[u8]) -> Result<World, ConwayError> {
+        let mut reader = Reader { bytes, offset: 0 };
+
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(invalid(0, "Not a binary world".into()));
+        }
+
+        let version = reader.take(1)?[0];
+
+        if version == 0 || version > VERSION {
+            return Err(invalid(MAGIC.len(), format!("Unsupported binary world version {} (this build reads up to {})", version, VERSION)));
+        }
+
+        let size = Vector { x: reader.length()?, y: reader.length()? };
+        let frames = u64::from_le_bytes(reader.array()?);
+        let rule = Rule { birth: reader.ranges()?, survival: reader.ranges()? };
+
+        // Checked before the world is allocated, so a huge header can't fill memory.
+        let cells = size.x as u64 * size.y as u64;
+        let grid_start = reader.offset;
+        let grid = match usize::try_from(cells.div_ceil(8)) {
+            Ok(count) => reader.take(count)?,
+            Err(_) => return Err(invalid(grid_start, format!("A {}x{} grid is too big to load", size.x, size.y))),
+        };
+
+        // The last byte's unused high bits must be clear, or saving again wouldn't give the same bytes.
+        let used_bits = cells % 8;
+
+        if used_bits > 0 && grid[grid.len() - 1] >> used_bits != 0 {
+            return Err(invalid(grid_start + grid.len() - 1, "Cells set past the end of the grid".into()));
+        }
+
+        if reader.offset < bytes.len() {
+            return Err(invalid(reader.offset, format!("{} bytes after the grid", bytes.len() - reader.offset)));
+        }
+
+        let mut world = World::empty(&size);
+        world.set_rule(rule);
+
+        for y in 0..size.y as usize {
+            for x in 0..size.x as usize {
+                let bit = y * size.x as usize + x;
+
+                if grid[bit / 8] & (1 << (bit % 8)) != 0 {
+                    world.set_alive(x, y, true);
+                }
+            }
+        }
+
+        world.frames = frames;
+        Ok(world)
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+fn invalid(offset: usize, context: String) -> ConwayError {
+    ConwayError::BinaryError { offset, context }
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, count: usize) -> Result<&'a [u8], ConwayError> {
+        let end = self.offset.checked_add(count).filter(|end| *end <= self.bytes.len());
+        let Some(end) = end else {
+            return Err(invalid(self.bytes.len(), format!("Ends {} bytes short", self.offset.saturating_add(count) - self.bytes.len())));
+        };
+
+        let taken = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(taken)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ConwayError> {
+        Ok(self.take(N)?.try_into().expect("take returns exactly N bytes"))
+    }
+
+    // A width or height, which has to fit the i32s worlds are sized in and can't be 0.
+    fn length(&mut self) -> Result<i32, ConwayError> {
+        let offset = self.offset;
+        let length = u32::from_le_bytes(self.array()?);
+
+        match i32::try_from(length) {
+            Ok(length) if length > 0 => Ok(length),
+            _ => Err(invalid(offset, format!("Invalid side length: {}", length))),
+        }
+    }
+
+    fn ranges(&mut self) -> Result<Vec<RangeInclusive<u32>>, ConwayError> {
+        let count = u16::from_le_bytes(self.array()?);
+
+        (0..count)
+            .map(|_| {
+                let offset = self.offset;
+                let (start, end) = (u32::from_le_bytes(self.array()?), u32::from_le_bytes(self.array()?));
+
+                match start <= end {
+                    true => Ok(start..=end),
+                    false => Err(invalid(offset, format!("Invalid rule range: {}..={}", start, end))),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // 13x7 is 91 cells, so the last of the 12 grid bytes only uses its lowest 3 bits.
+    fn saved() -> (World, Vec<u8>) {
+        let mut world = World::new(&Vector { x: 13, y: 7 }, 0.4, &mut StdRng::seed_from_u64(5));
+        world.set_rule("B36/S23".parse().unwrap());
+        world.frames = 17;
+
+        let bytes = world.save_bin();
+        (world, bytes)
+    }
+
+    fn error_offset(bytes: &[u8]) -> Option<usize> {
+        match World::load_bin(bytes) {
+            Err(ConwayError::BinaryError { offset, .. }) => Some(offset),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn round_trips_exactly() {
+        let (world, bytes) = saved();
+        let loaded = World::load_bin(&bytes).unwrap();
+
+        assert_eq!(loaded.save_bin(), bytes);
+        assert_eq!(loaded.snapshot(), world.snapshot());
+        assert_eq!(loaded.rule, world.rule);
+        assert_eq!(loaded.frames, 17);
+        assert_eq!(loaded.population, world.population);
+    }
+
+    #[test]
+    fn refuses_newer_versions() {
+        let (_, mut bytes) = saved();
+        bytes[MAGIC.len()] = VERSION + 1;
+
+        assert_eq!(error_offset(&bytes), Some(MAGIC.len()));
+    }
+
+    #[test]
+    fn refuses_grids_of_the_wrong_length_or_with_stray_bits() {
+        let (_, bytes) = saved();
+        let last = bytes.len() - 1;
+
+        assert_eq!(error_offset(&bytes[..last]), Some(last));
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(error_offset(&trailing), Some(bytes.len()));
+
+        let mut stray = bytes.clone();
+        stray[last] |= 1 << 3;
+        assert_eq!(error_offset(&stray), Some(last));
+    }
+
+    #[test]
+    fn a_huge_header_on_a_short_buffer_fails_before_allocating() {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(VERSION);
+        bytes.extend((i32::MAX as u32).to_le_bytes());
+        bytes.extend((i32::MAX as u32).to_le_bytes());
+        bytes.extend(0u64.to_le_bytes());
+        bytes.extend([0, 0, 0, 0]);
+
+        // Allocating the world first would need billions of gigabytes and abort the test.
+        assert_eq!(error_offset(&bytes), Some(bytes.len()));
+    }
+}
@@ -0,0 +1,258 @@
+// A one-bit-per-cell grid for very large worlds, ticking 64 cells at a time. It only covers the
+// plain case: Moore radius 1 with uniform weights and a synchronous update, on any topology but
+// the live edge.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use crate::rule::Rule;
+use crate::{Topology, UpdateScheme, Vector, World};
+
+const WORD_BITS: usize = 64;
+
+pub struct BitGrid {
+    pub size: Vector,
+    pub topology: Topology,
+    pub rule: Rule,
+    pub frames: u64,
+    words_per_row: usize,
+    words: Vec<u64>,
+    birth: [bool; 9],
+    survival: [bool; 9],
+}
+
+impl BitGrid {
+    // Panics unless both sides of `size` are positive, like `World::with_min`.
+    pub fn new(size: &Vector, topology: Topology, rule: Rule) -> BitGrid {
+        assert!(size.x > 0 && size.y > 0, "world size must be positive, got {}x{}", size.x, size.y);
+
+        let words_per_row = (size.x as usize).div_ceil(WORD_BITS);
+
+        BitGrid {
+            size: *size,
+            topology,
+            birth: core::array::from_fn(|count| rule.next_state(false, count as u32)),
+            survival: core::array::from_fn(|count| rule.next_state(true, count as u32)),
+            rule,
+            frames: 0,
+            words_per_row,
+            words: vec![0; words_per_row * size.y as usize],
+        }
+    }
+
+    // Copies a world's cells and settings, refusing the ones the word-wise tick can't follow.
+    pub fn from_world(world: &World) -> Result<BitGrid, String> {
+        if world.offsets.len() != 8 || world.offsets.iter().any(|offset| offset.weight != 1) {
+            return Err("BitGrid only supports the uniform Moore neighbourhood of radius 1".to_string());
+        }
+
+        if world.gravity || world.update_scheme != UpdateScheme::Synchronous {
+            return Err("BitGrid only supports synchronous updates without gravity".to_string());
+        }
+
+        if world.topology == Topology::LiveEdge {
+            return Err("BitGrid does not support the live edge topology".to_string());
+        }
+
+        if world.rule.next_state(false, 0) {
+            return Err(format!("BitGrid does not support B0 rules like {}", world.rule));
+        }
+
+        let mut grid = BitGrid::new(&world.size, world.topology, world.rule.clone());
+        grid.frames = world.frames;
+
+        for (x, column) in world.cells.iter().enumerate() {
+            for (y, cell) in column.iter().enumerate() {
+                grid.set(x, y, cell.is_alive());
+            }
+        }
+
+        Ok(grid)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.words[y * self.words_per_row + x / WORD_BITS] >> (x % WORD_BITS) & 1 == 1
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, alive: bool) {
+        let word = &mut self.words[y * self.words_per_row + x / WORD_BITS];
+        let bit = 1 << (x % WORD_BITS);
+
+        match alive {
+            true => *word |= bit,
+            false => *word &= !bit,
+        }
+    }
+
+    pub fn population(&self) -> u64 {
+        self.words.iter().map(|word| word.count_ones() as u64).sum()
+    }
+
+    // Bytes held for the cells themselves.
+    pub fn memory_bytes(&self) -> usize {
+        self.words.len() * core::mem::size_of::<u64>()
+    }
+
+    pub fn tick(&mut self) {
+        let height = self.size.y as usize;
+        let empty = vec![0; self.words_per_row];
+        let mut next = vec![0; self.words.len()];
+
+        for y in 0..height {
+            let above = match (y, self.topology.wraps_y()) {
+                (0, true) => self.row(height - 1),
+                (0, false) => &empty,
+                _ => self.row(y - 1),
+            };
+            let below = match (y + 1 == height, self.topology.wraps_y()) {
+                (true, true) => self.row(0),
+                (true, false) => &empty,
+                _ => self.row(y + 1),
+            };
+            let row = self.row(y);
+
+            for word in 0..self.words_per_row {
+                let mut counts = [0; 4];
+
+                for line in [above, below] {
+                    add_bit(&mut counts, line[word]);
+                    add_bit(&mut counts, self.west(line, word));
+                    add_bit(&mut counts, self.east(line, word));
+                }
+
+                add_bit(&mut counts, self.west(row, word));
+                add_bit(&mut counts, self.east(row, word));
+
+                let alive = row[word];
+                let mut born = 0;
+                let mut survived = 0;
+
+                for count in 0..=8 {
+                    let matches = count_equals(&counts, count);
+
+                    if self.birth[count] {
+                        born |= matches;
+                    }
+
+                    if self.survival[count] {
+                        survived |= matches;
+                    }
+                }
+
+                next[y * self.words_per_row + word] = ((alive & survived) | (!alive & born)) & self.word_mask(word);
+            }
+        }
+
+        self.words = next;
+        self.frames += 1;
+    }
+
+    fn row(&self, y: usize) -> &[u64] {
+        &self.words[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    // The bits past the right edge in the last word of a row, which are always kept clear.
+    fn word_mask(&self, word: usize) -> u64 {
+        let used = self.size.x as usize - word * WORD_BITS;
+
+        if used >= WORD_BITS { u64::MAX } else { (1 << used) - 1 }
+    }
+
+    // Each cell's left-hand neighbour, lined up with the cell.
+    fn west(&self, row: &[u64], word: usize) -> u64 {
+        let carry = match (word, self.topology.wraps_x()) {
+            (0, true) => (row[self.words_per_row - 1] >> ((self.size.x as usize - 1) % WORD_BITS)) & 1,
+            (0, false) => 0,
+            _ => row[word - 1] >> (WORD_BITS - 1),
+        };
+
+        (row[word] << 1) | carry
+    }
+
+    // Each cell's right-hand neighbour, lined up with the cell.
+    fn east(&self, row: &[u64], word: usize) -> u64 {
+        let last = word + 1 == self.words_per_row;
+        let shifted = row[word] >> 1;
+
+        match (last, self.topology.wraps_x()) {
+            (true, true) => shifted | (row[0] & 1) << ((self.size.x as usize - 1) % WORD_BITS),
+            (true, false) => shifted,
+            _ => shifted | row[word + 1] << (WORD_BITS - 1),
+        }
+    }
+}
+
+// Adds one bit per cell into counts held as four bit planes, least significant first.
+fn add_bit(counts: &mut [u64; 4], bit: u64) {
+    let mut carry = bit;
+
+    for plane in counts.iter_mut() {
+        let sum = *plane ^ carry;
+        carry &= *plane;
+        *plane = sum;
+    }
+}
+
+fn count_equals(counts: &[u64; 4], count: usize) -> u64 {
+    counts.iter()
+        .enumerate()
+        .fold(u64::MAX, |matches, (bit, plane)| match count >> bit & 1 {
+            1 => matches & plane,
+            _ => matches & !plane,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn ticks_like_a_world() {
+        // Widths either side of a word boundary, and rows too short to fill one.
+        let sizes = [Vector { x: 1, y: 1 }, Vector { x: 63, y: 9 }, Vector { x: 65, y: 7 }, Vector { x: 130, y: 3 }];
+        let topologies = [Topology::Bounded, Topology::WrapX, Topology::WrapY, Topology::Torus];
+
+        for (seed, size) in sizes.iter().enumerate() {
+            for topology in topologies {
+                for rule in ["B3/S23", "B36/S23", "B3678/S34678"] {
+                    let mut world = World::new(size, 0.35, &mut StdRng::seed_from_u64(seed as u64));
+                    world.set_topology(topology);
+                    world.set_rule(rule.parse().unwrap());
+                    let mut grid = BitGrid::from_world(&world).unwrap();
+
+                    for generation in 1..=30 {
+                        world.tick();
+                        grid.tick();
+
+                        for (x, column) in world.cells.iter().enumerate() {
+                            for (y, cell) in column.iter().enumerate() {
+                                assert_eq!(grid.get(x, y), cell.is_alive(), "{}x{} {} {} generation {}", size.x, size.y, topology.name(), rule, generation);
+                            }
+                        }
+                    }
+
+                    assert_eq!(grid.population(), world.population);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn refuses_worlds_it_cannot_follow() {
+        let mut world = World::empty(&Vector { x: 8, y: 8 });
+        world.set_radius(2);
+        assert!(BitGrid::from_world(&world).is_err());
+
+        let mut world = World::empty(&Vector { x: 8, y: 8 });
+        world.set_topology(Topology::LiveEdge);
+        assert!(BitGrid::from_world(&world).is_err());
+
+        let mut world = World::empty(&Vector { x: 8, y: 8 });
+        world.set_rule("B0/S8".parse().unwrap());
+        assert!(BitGrid::from_world(&world).is_err());
+    }
+}
@@ -0,0 +1,174 @@
+// Chainable construction for worlds, so new settings don't keep growing `World::new`. Anything
+// left unset matches a plain `World::new`.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::rule::{Rule, Weights};
+use crate::{Neighbourhood, Symmetry, Topology, Vector, World};
+
+/// Sets up a world one setting at a time, then draws its cells with [`build`](WorldBuilder::build).
+///
+/// A HighLife soup on a torus, which the same seed gives back cell for cell:
+///
+/// ```
+/// use rust_conway::builder::WorldBuilder;
+/// use rust_conway::{Topology, Vector};
+///
+/// let highlife = || {
+///     WorldBuilder::new(&Vector { x: 64, y: 48 })
+///         .density(0.35)
+///         .seed(42)
+///         .rule("B36/S23".parse().unwrap())
+///         .topology(Topology::Torus)
+///         .build()
+/// };
+///
+/// let mut world = highlife();
+/// assert_eq!(world.rule.to_string(), "B36/S23");
+/// assert!(world.topology == Topology::Torus);
+/// assert_eq!(world.seed, Some(42));
+/// assert_eq!(world.snapshot(), highlife().snapshot());
+///
+/// world.tick();
+/// assert_eq!(world.frames, 1);
+/// ```
+///
+/// Anything left unset matches [`World::new`]: a bounded, Moore radius 1 world under Conway's rule,
+/// half alive.
+///
+/// ```
+/// use rust_conway::builder::WorldBuilder;
+/// use rust_conway::{Neighbourhood, Topology, Vector};
+///
+/// let world = WorldBuilder::new(&Vector { x: 8, y: 8 }).build();
+/// assert_eq!(world.rule.to_string(), "B3/S23");
+/// assert!(world.topology == Topology::Bounded && world.neighbourhood == Neighbourhood::Moore);
+/// assert_eq!(world.radius, 1);
+/// ```
+pub struct WorldBuilder {
+    size: Vector,
+    density: f64,
+    seed: Option<u64>,
+    rule: Rule,
+    topology: Topology,
+    neighbourhood: Neighbourhood,
+    radius: i32,
+    symmetry: Option<Symmetry>,
+    blob: Option<i32>,
+}
+
+impl WorldBuilder {
+    pub fn new(size: &Vector) -> WorldBuilder {
+        WorldBuilder {
+            size: *size,
+            density: 0.5,
+            seed: None,
+            rule: Rule::conway(),
+            topology: Topology::Bounded,
+            neighbourhood: Neighbourhood::Moore,
+            radius: 1,
+            symmetry: None,
+            blob: None,
+        }
+    }
+
+    pub fn density(mut self, density: f64) -> WorldBuilder {
+        self.density = density;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> WorldBuilder {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn rule(mut self, rule: Rule) -> WorldBuilder {
+        self.rule = rule;
+        self
+    }
+
+    pub fn topology(mut self, topology: Topology) -> WorldBuilder {
+        self.topology = topology;
+        self
+    }
+
+    pub fn neighbourhood(mut self, neighbourhood: Neighbourhood) -> WorldBuilder {
+        self.neighbourhood = neighbourhood;
+        self
+    }
+
+    pub fn radius(mut self, radius: i32) -> WorldBuilder {
+        self.radius = radius;
+        self
+    }
+
+    pub fn symmetry(mut self, symmetry: Symmetry) -> WorldBuilder {
+        self.symmetry = Some(symmetry);
+        self
+    }
+
+    // Keeps the random cells to a disc of `radius` around the centre, leaving the rest dead. The
+    // whole grid is still drawn first, so a seed's blob is the middle of the world it gives alone.
+    pub fn blob(mut self, radius: i32) -> WorldBuilder {
+        self.blob = Some(radius);
+        self
+    }
+
+    // Panics unless both sides of the size are positive, like `World::new`, and unless any
+    // symmetry fits the size. The seed is kept on the world either way; without std there is
+    // nothing to draw a fresh one from, so it is 0.
+    pub fn build(self) -> World {
+        let seed = self.seed.unwrap_or_else(fresh_seed);
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let mut world = match self.symmetry {
+            Some(symmetry) => {
+                let mut world = World::empty(&self.size);
+                world.randomize_symmetric(self.density, symmetry, &mut rng);
+                world
+            }
+            None => World::new(&self.size, self.density, &mut rng),
+        };
+
+        if let Some(radius) = self.blob {
+            let centre = Vector { x: self.size.x / 2, y: self.size.y / 2 };
+
+            for x in 0..self.size.x {
+                for y in 0..self.size.y {
+                    let (dx, dy) = ((x - centre.x) as i64, (y - centre.y) as i64);
+
+                    if dx * dx + dy * dy > radius as i64 * radius as i64 {
+                        world.set_alive(x as usize, y as usize, false);
+                    }
+                }
+            }
+        }
+
+        world.seed = Some(seed);
+        world.set_rule(self.rule);
+
+        // Counting the neighbours again is only worth it when their layout changed.
+        if (self.topology, self.neighbourhood, self.radius) != (world.topology, world.neighbourhood, world.radius) {
+            world.topology = self.topology;
+            world.neighbourhood = self.neighbourhood;
+            world.radius = self.radius;
+            world.weights = Weights::uniform(self.radius);
+            world.update_offsets();
+        }
+
+        // Both of the above forget the history the first generation was hashed into.
+        world.update_period(false);
+        world
+    }
+}
+
+#[cfg(feature = "std")]
+fn fresh_seed() -> u64 {
+    rand::random()
+}
+
+#[cfg(not(feature = "std"))]
+fn fresh_seed() -> u64 {
+    0
+}
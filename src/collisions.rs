@@ -0,0 +1,74 @@
+// Curated collisions between catalog patterns, laid out so they meet within a few dozen generations.
+
+use alloc::vec::Vec;
+use crate::pattern::{Orientation, Pattern};
+use crate::{Vector, World};
+
+pub struct Piece {
+    pub pattern: &'static str,
+    pub orientation: Orientation,
+    pub at: Vector,
+}
+
+pub struct Collision {
+    pub name: &'static str,
+    pub pieces: &'static [Piece],
+}
+
+pub const COLLISIONS: &[Collision] = &[
+    Collision {
+        name: "two gliders make a block",
+        pieces: &[
+            Piece { pattern: "glider", orientation: Orientation::Identity, at: Vector { x: 0, y: 0 } },
+            Piece { pattern: "glider", orientation: Orientation::Rotate180, at: Vector { x: 9, y: 9 } },
+        ],
+    },
+    Collision {
+        name: "two gliders annihilate",
+        pieces: &[
+            Piece { pattern: "glider", orientation: Orientation::Identity, at: Vector { x: 0, y: 0 } },
+            Piece { pattern: "glider", orientation: Orientation::Rotate180, at: Vector { x: 10, y: 10 } },
+        ],
+    },
+    Collision {
+        name: "glider and block annihilate",
+        pieces: &[
+            Piece { pattern: "glider", orientation: Orientation::Identity, at: Vector { x: 0, y: 0 } },
+            Piece { pattern: "block", orientation: Orientation::Identity, at: Vector { x: 9, y: 9 } },
+        ],
+    },
+    Collision {
+        name: "glider and blinker make a ship",
+        pieces: &[
+            Piece { pattern: "glider", orientation: Orientation::Identity, at: Vector { x: 0, y: 0 } },
+            Piece { pattern: "blinker", orientation: Orientation::Identity, at: Vector { x: 9, y: 6 } },
+        ],
+    },
+];
+
+impl Collision {
+    // A fresh world with the pieces centred in it.
+    pub fn world(&self, size: &Vector) -> World {
+        let pieces: Vec<(Pattern, Vector)> = self.pieces.iter()
+            .filter_map(|piece| Some((Pattern::named(piece.pattern)?.oriented(piece.orientation), piece.at)))
+            .collect();
+
+        let extent = pieces.iter().fold(Vector { x: 0, y: 0 }, |extent, (pattern, at)| Vector {
+            x: extent.x.max(at.x + pattern.size.x),
+            y: extent.y.max(at.y + pattern.size.y),
+        });
+
+        let origin = Vector {
+            x: (size.x - extent.x) / 2,
+            y: (size.y - extent.y) / 2,
+        };
+
+        let mut world = World::empty(size);
+
+        for (pattern, at) in &pieces {
+            world.stamp(pattern, &Vector { x: origin.x + at.x, y: origin.y + at.y });
+        }
+
+        world
+    }
+}
@@ -0,0 +1,308 @@
+// Connected structures of live cells, and colours that follow them between generations.
+
+use std::collections::HashMap;
+
+use ratatui::style::Color;
+
+use crate::{CellState, Vector, World};
+
+const PALETTE: [Color; 12] = [
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+];
+
+impl<S: CellState> World<S> {
+    // Labels each live cell with its 8-connected component, starting from 1; dead cells are 0.
+    pub fn label_components(&self) -> (Vec<Vec<usize>>, usize) {
+        let mut labels = vec![vec![0; self.size.y as usize]; self.size.x as usize];
+        let mut count = 0;
+        let mut stack = Vec::new();
+
+        for x in 0..self.size.x as usize {
+            for y in 0..self.size.y as usize {
+                if labels[x][y] != 0 || !self.cells[x][y].is_alive() {
+                    continue;
+                }
+
+                count += 1;
+                labels[x][y] = count;
+                stack.push(Vector { x: x as i32, y: y as i32 });
+
+                while let Some(position) = stack.pop() {
+                    for dx in -1..=1 {
+                        for dy in -1..=1 {
+                            let neighbour = self.topology.wrap(
+                                &Vector { x: position.x + dx, y: position.y + dy },
+                                &Vector { x: 0, y: 0 },
+                                &self.size,
+                            );
+
+                            if neighbour.out_of_bounds(&Vector { x: 0, y: 0 }, &self.size) {
+                                continue;
+                            }
+
+                            let (nx, ny) = (neighbour.x as usize, neighbour.y as usize);
+
+                            if labels[nx][ny] == 0 && self.cells[nx][ny].is_alive() {
+                                labels[nx][ny] = count;
+                                stack.push(neighbour);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        (labels, count)
+    }
+
+    // The average position of each component's cells, in label order, so index 0 is label 1.
+    // Like `World::centroid` it takes no account of wrapping.
+    pub fn component_centroids(&self) -> Vec<Vector> {
+        let (labels, count) = self.label_components();
+        let mut sums = vec![(0i64, 0i64, 0i64); count];
+
+        for (x, column) in labels.iter().enumerate() {
+            for (y, &label) in column.iter().enumerate() {
+                if label != 0 {
+                    let sum = &mut sums[label - 1];
+                    *sum = (sum.0 + x as i64, sum.1 + y as i64, sum.2 + 1);
+                }
+            }
+        }
+
+        sums.into_iter()
+            .map(|(x, y, cells)| Vector { x: (x / cells) as i32, y: (y / cells) as i32 })
+            .collect()
+    }
+
+    // The smallest box holding every live cell as (corner, size). On a wrapped axis the box may
+    // start near the far edge and continue across the seam, so `corner + size` can exceed the world.
+    pub fn live_bounds(&self) -> Option<(Vector, Vector)> {
+        let mut columns = vec![false; self.size.x as usize];
+        let mut rows = vec![false; self.size.y as usize];
+
+        for (x, column) in self.cells.iter().enumerate() {
+            for (y, cell) in column.iter().enumerate() {
+                if cell.is_alive() {
+                    columns[x] = true;
+                    rows[y] = true;
+                }
+            }
+        }
+
+        let (x, width) = axis_bounds(&columns, self.topology.wraps_x())?;
+        let (y, height) = axis_bounds(&rows, self.topology.wraps_y())?;
+
+        Some((Vector { x, y }, Vector { x: width, y: height }))
+    }
+}
+
+// The start and length of the shortest run covering every occupied position, which on a wrapped
+// axis begins just after the longest circular gap.
+fn axis_bounds(occupied: &[bool], wraps: bool) -> Option<(i32, i32)> {
+    let first = occupied.iter().position(|&occupied| occupied)? as i32;
+    let last = occupied.iter().rposition(|&occupied| occupied)? as i32;
+    let length = occupied.len() as i32;
+
+    if !wraps {
+        return Some((first, last - first + 1));
+    }
+
+    let mut gap = (length - 1 - last + first, first);
+    let mut run = 0;
+
+    for (position, &occupied) in occupied.iter().enumerate() {
+        match occupied {
+            false => run += 1,
+            true => {
+                if run > gap.0 {
+                    gap = (run, position as i32);
+                }
+
+                run = 0;
+            }
+        }
+    }
+
+    Some((gap.1, length - gap.0))
+}
+
+#[derive(Default)]
+pub struct ComponentColours {
+    labels: Vec<Vec<usize>>,
+    colours: Vec<usize>,
+    next_colour: usize,
+}
+
+impl ComponentColours {
+    // Each new component inherits the colour of the old component it overlaps most, so the
+    // larger half of a split and the larger parent of a merge keep their colour.
+    pub fn update<S: CellState>(&mut self, world: &World<S>) {
+        let (labels, count) = world.label_components();
+        let mut overlaps: HashMap<(usize, usize), u32> = HashMap::new();
+
+        for (column, previous_column) in labels.iter().zip(&self.labels) {
+            for (&label, &previous_label) in column.iter().zip(previous_column) {
+                if label != 0 && previous_label != 0 {
+                    *overlaps.entry((label, previous_label)).or_default() += 1;
+                }
+            }
+        }
+
+        // Ties go to the lowest labels, which follow the scan order, so the colours never depend
+        // on the HashMap's order and a seed gets the same colours on every run.
+        let mut overlaps: Vec<((usize, usize), u32)> = overlaps.into_iter().collect();
+        overlaps.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        let mut colours = vec![None; count + 1];
+        let mut claimed = vec![false; self.colours.len()];
+
+        for ((label, previous_label), _) in overlaps {
+            if colours[label].is_none() && !claimed[previous_label] {
+                colours[label] = Some(self.colours[previous_label]);
+                claimed[previous_label] = true;
+            }
+        }
+
+        self.colours = colours.into_iter()
+            .map(|colour| colour.unwrap_or_else(|| {
+                self.next_colour += 1;
+                self.next_colour
+            }))
+            .collect();
+        self.labels = labels;
+    }
+
+    pub fn count(&self) -> usize {
+        self.colours.len().saturating_sub(1)
+    }
+
+    pub fn colour_at(&self, x: i32, y: i32) -> Option<Color> {
+        match self.labels.get(x as usize)?.get(y as usize)? {
+            0 => None,
+            &label => Some(PALETTE[self.colours[label] % PALETTE.len()]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Topology;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // A horizontal blinker centred on the left/right seam of a 6x5 world.
+    fn blinker_on_the_seam(topology: Topology) -> World {
+        let mut world = World::empty(&Vector { x: 6, y: 5 });
+        world.set_topology(topology);
+
+        for x in [5, 0, 1] {
+            world.set_alive(x, 2, true);
+        }
+
+        world
+    }
+
+    #[test]
+    fn a_blinker_across_the_seam_is_one_component() {
+        let world = blinker_on_the_seam(Topology::Torus);
+
+        assert_eq!(world.label_components().1, 1);
+        assert_eq!(world.live_bounds(), Some((Vector { x: 5, y: 2 }, Vector { x: 3, y: 1 })));
+    }
+
+    #[test]
+    fn the_seam_splits_components_on_a_bounded_world() {
+        let world = blinker_on_the_seam(Topology::Bounded);
+
+        assert_eq!(world.label_components().1, 2);
+        assert_eq!(world.live_bounds(), Some((Vector { x: 0, y: 2 }, Vector { x: 6, y: 1 })));
+    }
+
+    #[test]
+    fn a_blinker_stays_one_component_as_it_turns_across_the_seam() {
+        let mut world = blinker_on_the_seam(Topology::WrapX);
+
+        for _ in 0..4 {
+            world.tick();
+            assert_eq!(world.population, 3);
+            assert_eq!(world.label_components().1, 1);
+        }
+    }
+
+    #[test]
+    fn a_seed_gets_the_same_colours_every_run() {
+        let size = Vector { x: 24, y: 16 };
+        let seeded = || {
+            let mut world = World::new(&size, 0.4, &mut StdRng::seed_from_u64(11));
+            world.set_topology(Topology::Torus);
+            world
+        };
+        let (mut first, mut second) = (seeded(), seeded());
+        let (mut first_colours, mut second_colours) = (ComponentColours::default(), ComponentColours::default());
+
+        for generation in 0..40 {
+            first_colours.update(&first);
+            second_colours.update(&second);
+
+            for x in 0..size.x {
+                for y in 0..size.y {
+                    assert_eq!(first_colours.colour_at(x, y), second_colours.colour_at(x, y), "{},{} in generation {}", x, y, generation);
+                }
+            }
+
+            first.tick();
+            second.tick();
+        }
+    }
+
+    // A row of cells, all on row 2 of a 7x5 world.
+    fn row(cells: &[usize]) -> World {
+        let mut world = World::empty(&Vector { x: 7, y: 5 });
+
+        for &x in cells {
+            world.set_alive(x, 2, true);
+        }
+
+        world
+    }
+
+    #[test]
+    fn overlap_ties_go_to_the_lowest_labels() {
+        // Each run has a fresh HashMap, so a tie left to its order would come out differently
+        // some of the time.
+        for _ in 0..20 {
+            // An even split: both halves overlap the old line by two cells, and the left one,
+            // labelled first, keeps its colour.
+            let mut colours = ComponentColours::default();
+            colours.update(&row(&[1, 2, 3, 4, 5]));
+            let line = colours.colour_at(1, 2);
+
+            colours.update(&row(&[1, 2, 4, 5]));
+            assert_eq!(colours.count(), 2);
+            assert_eq!(colours.colour_at(1, 2), line);
+            assert_ne!(colours.colour_at(4, 2), line);
+
+            // An even merge takes the colour of the left parent.
+            let mut colours = ComponentColours::default();
+            colours.update(&row(&[1, 2, 4, 5]));
+            let left = colours.colour_at(1, 2);
+
+            colours.update(&row(&[1, 2, 3, 4, 5]));
+            assert_eq!(colours.count(), 1);
+            assert_eq!(colours.colour_at(5, 2), left);
+        }
+    }
+}
@@ -0,0 +1,84 @@
+// Cells that can't be born again for a while after dying, like the refractory period of the
+// Generations family of rules. The rule still decides births and survivals as usual.
+
+use crate::rule::Rule;
+use crate::CellState;
+
+// `COOLDOWN` is how many generations a cell stays dead for, whatever its neighbours, before it
+// can be born again. A cooling cell counts as dead to its neighbours.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Cooling<const COOLDOWN: u32> {
+    pub alive: bool,
+    pub cooldown: u32,
+}
+
+impl<const COOLDOWN: u32> CellState for Cooling<COOLDOWN> {
+    fn dead() -> Self {
+        Cooling { alive: false, cooldown: 0 }
+    }
+
+    fn live() -> Self {
+        Cooling { alive: true, cooldown: 0 }
+    }
+
+    fn is_live_for_counting(&self) -> bool {
+        self.alive
+    }
+
+    fn next(&self, neighbour_sum: u32, rule: &Rule) -> Self {
+        match (self.alive, self.cooldown) {
+            (true, _) if rule.next_state(true, neighbour_sum) => *self,
+            (true, _) => Cooling { alive: false, cooldown: COOLDOWN },
+            (false, 0) => Cooling { alive: rule.next_state(false, neighbour_sum), cooldown: 0 },
+            (false, cooldown) => Cooling { alive: false, cooldown: cooldown - 1 },
+        }
+    }
+
+    fn cooldown(&self) -> u32 {
+        self.cooldown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Vector, World};
+    use rand::rngs::mock::StepRng;
+
+    #[test]
+    fn a_cooling_cell_ignores_its_neighbours_until_it_has_cooled() {
+        let life: Rule = "life".parse().unwrap();
+        let mut cell = Cooling::<3> { alive: false, cooldown: 3 };
+
+        // Three neighbours would give birth under B3 every generation, but only once it has cooled.
+        for cooldown in [2, 1, 0] {
+            cell = cell.next(3, &life);
+            assert_eq!(cell, Cooling { alive: false, cooldown });
+        }
+
+        assert_eq!(cell.next(3, &life), Cooling::live());
+    }
+
+    #[test]
+    fn cells_cool_down_in_a_world() {
+        let mut world: World<Cooling<2>> = World::with_min(&Vector { x: 0, y: 0 }, &Vector { x: 2, y: 1 }, 0.0, &mut StepRng::new(0, 0));
+        world.set_rule("B1/S0".parse().unwrap());
+        world.set_alive(0, 0, true);
+        world.set_state(1, 0, Cooling { alive: false, cooldown: 2 });
+
+        // The lone live cell survives on S0 while its neighbour sees it without being born.
+        for cooldown in [1, 0] {
+            world.tick();
+            assert_eq!(world.cells[0][0].state, Cooling::live());
+            assert_eq!(world.cells[1][0].state, Cooling { alive: false, cooldown });
+        }
+
+        world.tick();
+        assert_eq!(world.cells[1][0].state, Cooling::live());
+
+        // Both now have a live neighbour, which S0 doesn't keep, so both start cooling.
+        world.tick();
+        assert_eq!(world.cells[0][0].state, Cooling { alive: false, cooldown: 2 });
+        assert_eq!(world.cells[1][0].state, Cooling { alive: false, cooldown: 2 });
+    }
+}
@@ -0,0 +1,83 @@
+// Exact grids as comma-separated 0/1 values, one row of the world per line.
+
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use crate::error::ConwayError;
+use crate::pattern::{Metadata, Pattern};
+use crate::{Vector, World};
+
+pub fn parse(input: &str) -> Result<Pattern, ConwayError> {
+    let invalid = |line, context| ConwayError::ParseError { line, context };
+    let mut size = Vector { x: 0, y: 0 };
+    let mut cells = Vec::new();
+
+    for (i, line) in input.lines().map(str::trim).enumerate().filter(|(_, line)| !line.is_empty()) {
+        let values: Vec<&str> = line.split(',').map(str::trim).collect();
+
+        if size.y == 0 {
+            size.x = values.len() as i32;
+        } else if values.len() as i32 != size.x {
+            return Err(invalid(i + 1, format!("CSV row {} has {} values, expected {}", size.y + 1, values.len(), size.x)));
+        }
+
+        for (x, value) in values.iter().enumerate() {
+            match *value {
+                "1" => cells.push(Vector { x: x as i32, y: size.y }),
+                "0" => {}
+                value => return Err(invalid(i + 1, format!("Invalid CSV cell on row {}: {}", size.y + 1, value))),
+            }
+        }
+
+        size.y += 1;
+    }
+
+    if size.y == 0 {
+        return Err(invalid(input.lines().count() + 1, "Empty CSV grid".to_string()));
+    }
+
+    Ok(Pattern { size, cells, rule: None, metadata: Metadata::default() })
+}
+
+impl World {
+    pub fn from_csv(text: &str) -> Result<World, ConwayError> {
+        let pattern = parse(text)?;
+
+        Ok(World::from_pattern(&pattern, &pattern.size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn imports_a_blinker() {
+        let mut world = World::from_csv("0,0,0\n1,1,1\n0,0,0\n").unwrap();
+        let horizontal = [Vector { x: 0, y: 1 }, Vector { x: 1, y: 1 }, Vector { x: 2, y: 1 }];
+
+        assert_eq!(world.size, Vector { x: 3, y: 3 });
+        assert_eq!(world.alive_cells().collect::<Vec<_>>(), horizontal);
+
+        world.tick();
+        assert_eq!(world.alive_cells().collect::<Vec<_>>(), [Vector { x: 1, y: 0 }, Vector { x: 1, y: 1 }, Vector { x: 1, y: 2 }]);
+
+        world.tick();
+        assert_eq!(world.alive_cells().collect::<Vec<_>>(), horizontal);
+    }
+
+    #[test]
+    fn rejects_ragged_rows_and_other_values() {
+        match World::from_csv("0,1,0\n1,1\n") {
+            Err(ConwayError::ParseError { line: 2, .. }) => {}
+            other => panic!("expected a parse error on line 2, got {:?}", other.map(|world| world.size)),
+        }
+
+        match World::from_csv(" 0, 1\n\n0, 2\n") {
+            Err(ConwayError::ParseError { line: 3, .. }) => {}
+            other => panic!("expected a parse error on line 3, got {:?}", other.map(|world| world.size)),
+        }
+
+        assert!(World::from_csv("\n\n").is_err());
+    }
+}
@@ -0,0 +1,99 @@
+// Fixed teaching setups that come out identical on every run, with no random seeding.
+
+use crate::collisions::Piece;
+use crate::pattern::{Orientation, Pattern};
+use crate::{Topology, Vector, World};
+
+pub struct Demo {
+    pub name: &'static str,
+    pub size: Vector,
+    pub topology: Topology,
+    // A rule string or preset name, as --rule takes.
+    pub rule: &'static str,
+    pub pieces: &'static [Piece],
+    pub starts_paused: bool,
+}
+
+// A glider moves one cell diagonally every four generations (c/4), so on a 16x16 torus it
+// comes back to where it started after 64.
+pub const DEMOS: &[Demo] = &[
+    Demo {
+        name: "torus-drift",
+        size: Vector { x: 16, y: 16 },
+        topology: Topology::Torus,
+        rule: "life",
+        pieces: &[
+            Piece { pattern: "glider", orientation: Orientation::Identity, at: Vector { x: 1, y: 1 } },
+        ],
+        starts_paused: true,
+    },
+    // Under Replicator every pattern copies itself: after 2^n generations a single cell has been
+    // replaced by eight copies 2^n cells away. On a torus with a power of two side the copies
+    // cancel out where they meet across the edges, leaving nothing, so this one is odd.
+    Demo {
+        name: "replicator",
+        size: Vector { x: 63, y: 63 },
+        topology: Topology::Torus,
+        rule: "replicator",
+        pieces: &[
+            Piece { pattern: "cell", orientation: Orientation::Identity, at: Vector { x: 31, y: 31 } },
+        ],
+        starts_paused: false,
+    },
+];
+
+impl Demo {
+    pub fn named(name: &str) -> Option<&'static Demo> {
+        DEMOS.iter().find(|demo| demo.name == name)
+    }
+
+    pub fn world(&self) -> World {
+        let mut world = World::empty(&self.size);
+        world.set_topology(self.topology);
+
+        if let Ok(rule) = self.rule.parse() {
+            world.set_rule(rule);
+        }
+
+        for piece in self.pieces {
+            if let Some(pattern) = Pattern::named(piece.pattern) {
+                world.stamp(&pattern.oriented(piece.orientation), &piece.at);
+            }
+        }
+
+        world
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn the_replicator_copies_its_cell_every_power_of_two() {
+        let mut world = Demo::named("replicator").unwrap().world();
+
+        for generation in 1..=16u32 {
+            world.tick();
+
+            if !generation.is_power_of_two() {
+                continue;
+            }
+
+            // Eight copies of the seed cell `generation` away, and the seed itself gone.
+            let distance = generation as i32;
+            let mut expected = Vec::new();
+
+            for dx in [-distance, 0, distance] {
+                for dy in [-distance, 0, distance] {
+                    if dx != 0 || dy != 0 {
+                        expected.push(Vector { x: 31 + dx, y: 31 + dy });
+                    }
+                }
+            }
+
+            assert_eq!(world.alive_cells().collect::<Vec<Vector>>(), expected, "generation {}", generation);
+        }
+    }
+}
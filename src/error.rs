@@ -0,0 +1,64 @@
+// Errors from reading and writing patterns, for library callers to match on rather than parse
+// messages out of strings.
+
+use alloc::string::String;
+use alloc::string::ToString;
+use core::fmt;
+
+use crate::Vector;
+
+#[derive(Debug)]
+pub enum ConwayError {
+    // `line` counts from 1, and is one past the last line when the input ends too soon.
+    ParseError { line: usize, context: String },
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+    OutOfBounds { cell: Vector, size: Vector },
+    // Like ParseError, for binary worlds, where `offset` counts bytes from 0.
+    BinaryError { offset: usize, context: String },
+}
+
+impl fmt::Display for ConwayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConwayError::ParseError { line, context } => write!(f, "Line {}: {}", line, context),
+            #[cfg(feature = "std")]
+            ConwayError::Io(error) => write!(f, "{}", error),
+            ConwayError::OutOfBounds { cell, size } => {
+                write!(f, "Cell ({}, {}) is outside the {}x{} pattern", cell.x, cell.y, size.x, size.y)
+            }
+            ConwayError::BinaryError { offset, context } => write!(f, "Byte {}: {}", offset, context),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConwayError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConwayError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for ConwayError {
+    fn from(error: std::io::Error) -> ConwayError {
+        ConwayError::Io(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<png::EncodingError> for ConwayError {
+    fn from(error: png::EncodingError) -> ConwayError {
+        ConwayError::Io(error.into())
+    }
+}
+
+// The binary reports everything as plain messages.
+impl From<ConwayError> for String {
+    fn from(error: ConwayError) -> String {
+        error.to_string()
+    }
+}
@@ -0,0 +1,188 @@
+// Headless exporters for recording runs to disk.
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::str::FromStr;
+
+use crate::{CellState, Vector, World};
+use crate::error::ConwayError;
+use crate::render::Renderer;
+
+const LIVE_COLOUR: [u8; 3] = [255, 255, 255];
+const DEAD_COLOUR: [u8; 3] = [0, 0, 0];
+const CAPTION_COLOUR: [u8; 3] = [255, 200, 0];
+// Glyphs are 3 by 5 with a gap of one after each, drawn a cell to each pixel of the font.
+const GLYPH_ROWS: usize = 5;
+const GLYPH_ADVANCE: usize = 4;
+
+// Where the watermark caption sits on each frame.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl FromStr for Corner {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Corner, String> {
+        match name {
+            "top-left" => Ok(Corner::TopLeft),
+            "top-right" => Ok(Corner::TopRight),
+            "bottom-left" => Ok(Corner::BottomLeft),
+            "bottom-right" => Ok(Corner::BottomRight),
+            _ => Err(format!("Unknown corner: {} (use top-left, top-right, bottom-left or bottom-right)", name)),
+        }
+    }
+}
+
+// Frames as RGB rows, `scale` pixels to a cell. With `tile`, each wrapping axis is drawn three
+// times over so whatever crosses the seam is seen joined up. A `watermark` burns the generation,
+// rule and seed into that corner, clipped to the frame when it doesn't fit.
+pub struct PngRenderer {
+    pub scale: u32,
+    pub tile: bool,
+    pub watermark: Option<Corner>,
+}
+
+impl PngRenderer {
+    pub fn dimensions<S>(&self, world: &World<S>) -> (u32, u32) {
+        let tiles = self.tiles(world);
+
+        ((world.size.x * tiles.x) as u32 * self.scale, (world.size.y * tiles.y) as u32 * self.scale)
+    }
+
+    fn tiles<S>(&self, world: &World<S>) -> Vector {
+        Vector {
+            x: if self.tile && world.topology.wraps_x() { 3 } else { 1 },
+            y: if self.tile && world.topology.wraps_y() { 3 } else { 1 },
+        }
+    }
+
+    // The caption on a dark box with a margin of one font pixel all round.
+    fn draw_caption(&self, data: &mut [u8], (width, height): (u32, u32), text: &str, corner: Corner) {
+        let glyphs: Vec<[u8; GLYPH_ROWS]> = text.chars().map(glyph).collect();
+        let pixel = self.scale as usize;
+        let (width, height) = (width as usize, height as usize);
+        let (columns, rows) = (glyphs.len() * GLYPH_ADVANCE + 1, GLYPH_ROWS + 2);
+
+        let left = match corner {
+            Corner::TopLeft | Corner::BottomLeft => 0,
+            Corner::TopRight | Corner::BottomRight => width.saturating_sub(columns * pixel),
+        };
+        let top = match corner {
+            Corner::TopLeft | Corner::TopRight => 0,
+            Corner::BottomLeft | Corner::BottomRight => height.saturating_sub(rows * pixel),
+        };
+
+        for row in 0..rows {
+            for column in 0..columns {
+                let lit = (1..=GLYPH_ROWS).contains(&row) && column > 0 && {
+                    let bit = (column - 1) % GLYPH_ADVANCE;
+                    bit < 3 && glyphs[(column - 1) / GLYPH_ADVANCE][row - 1] & (0b100 >> bit) != 0
+                };
+                let colour = if lit { CAPTION_COLOUR } else { DEAD_COLOUR };
+
+                for py in (top + row * pixel..top + (row + 1) * pixel).filter(|py| *py < height) {
+                    for px in (left + column * pixel..left + (column + 1) * pixel).filter(|px| *px < width) {
+                        let at = (py * width + px) * 3;
+                        data[at..at + 3].copy_from_slice(&colour);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn caption<S>(world: &World<S>) -> String {
+    match world.seed {
+        Some(seed) => format!("GEN {} {} SEED {}", world.frames, world.rule, seed),
+        None => format!("GEN {} {}", world.frames, world.rule),
+    }
+}
+
+// Rows from the top, the left column in the highest of the three bits. Only what captions use
+// is here; anything else is left blank.
+fn glyph(character: char) -> [u8; GLYPH_ROWS] {
+    match character {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'G' => [0b111, 0b100, 0b101, 0b101, 0b111],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        '/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+        ',' => [0b000, 0b000, 0b000, 0b010, 0b100],
+        _ => [0; GLYPH_ROWS],
+    }
+}
+
+impl<S: CellState> Renderer<S> for PngRenderer {
+    type Output = Vec<u8>;
+
+    fn render(&self, world: &World<S>) -> Vec<u8> {
+        let scale = self.scale as usize;
+        let (columns, rows) = (world.size.x as usize, world.size.y as usize);
+        let (width, height) = self.dimensions(world);
+
+        let mut data = Vec::with_capacity(width as usize * height as usize * 3);
+
+        for py in 0..height as usize {
+            for px in 0..width as usize {
+                let colour = match world.cells[px / scale % columns][py / scale % rows].is_alive() {
+                    true => LIVE_COLOUR,
+                    false => DEAD_COLOUR,
+                };
+
+                data.extend_from_slice(&colour);
+            }
+        }
+
+        if let Some(corner) = self.watermark {
+            self.draw_caption(&mut data, (width, height), &caption(world), corner);
+        }
+
+        data
+    }
+}
+
+// Runs `frames` generations, counting the starting one, but only encodes every `every`th of
+// them plus the last, so long runs fit in a short animation.
+pub fn write_apng(world: &mut World, path: &str, frames: u32, every: u32, delay_ms: u16, renderer: &PngRenderer) -> Result<(), ConwayError> {
+    let every = every.max(1);
+    let sampled = |frame: u32| frame.is_multiple_of(every) || frame + 1 == frames;
+    let (width, height) = renderer.dimensions(world);
+
+    let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), width, height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_animated((0..frames).filter(|frame| sampled(*frame)).count() as u32, 0)?;
+    encoder.set_frame_delay(delay_ms, 1000)?;
+
+    let mut writer = encoder.write_header()?;
+
+    for frame in 0..frames {
+        if frame > 0 {
+            world.tick();
+        }
+
+        if sampled(frame) {
+            writer.write_image_data(&renderer.render(world))?;
+        }
+    }
+
+    writer.finish()?;
+    Ok(())
+}
@@ -0,0 +1,2042 @@
+// The Game of Life engine, kept free of terminal input and output so it can be driven as a library.
+// Without the `std` feature only the engine itself is built, on `core` and `alloc`. The `wasm`
+// feature links std back in for its exports, but none of the terminal or PNG code.
+
+#![cfg_attr(not(any(feature = "std", feature = "wasm")), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "bitgrid")]
+pub mod bitgrid;
+pub mod binary;
+pub mod builder;
+pub mod collisions;
+#[cfg(feature = "std")]
+pub mod components;
+pub mod cooldown;
+pub mod csv;
+pub mod demos;
+pub mod error;
+#[cfg(feature = "std")]
+pub mod export;
+pub mod pattern;
+#[cfg(feature = "std")]
+pub mod predecessor;
+pub mod render;
+pub mod rle;
+pub mod rule;
+#[cfg(feature = "serde")]
+pub mod summary;
+pub mod trails;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::{max, min};
+use core::hash::{Hash, Hasher};
+use core::ops::Range;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use ratatui::{
+    layout::Rect,
+    prelude::Stylize,
+    style::{Color, Style},
+    text::{Line, Span, Text},
+};
+use rand::Rng;
+use rand::rngs::mock::StepRng;
+#[cfg(feature = "std")]
+use crate::components::ComponentColours;
+use crate::pattern::{Metadata, Pattern};
+use crate::rule::{LargerThanLife, Rule, Weights};
+#[cfg(feature = "std")]
+use crate::trails::Trails;
+
+pub const DEFAULT_WORLD_MIN: Vector = Vector { x: 0, y: 0 };
+pub const POPULATION_HISTORY_LENGTH: usize = 60;
+const STATE_HISTORY_LENGTH: usize = 64;
+// How far the population may stray from its mean, and how far the means of the two halves of
+// the history may drift apart, as percentages of the mean, for `equilibrium`.
+const EQUILIBRIUM_BAND_PERCENT: u64 = 5;
+const EQUILIBRIUM_DRIFT_PERCENT: u64 = 1;
+const GRAVITY_NEIGHBOUR_THRESHOLD: u32 = 2;
+#[cfg(feature = "std")]
+const EMPTY_WORLD_MESSAGE: &str = "(empty world)";
+// Sums of 8 or more share the last colour.
+#[cfg(feature = "std")]
+const NEIGHBOUR_COUNT_COLOURS: [Color; 9] = [
+    Color::Black,
+    Color::Blue,
+    Color::Cyan,
+    Color::Green,
+    Color::Yellow,
+    Color::LightRed,
+    Color::Red,
+    Color::Magenta,
+    Color::White,
+];
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Neighbourhood {
+    Moore,
+    VonNeumann,
+    // Six neighbours, with the grid read as rows of hexagons where every odd row (counting from
+    // 0 at the top) sits half a cell to the right. A cell on an even row touches the two above
+    // and below it to the left, and one on an odd row the two to the right, besides the cells
+    // beside it. A torus of odd height puts two even rows against each other at the seam, so
+    // the hexagons across it don't quite line up.
+    Hexagonal,
+}
+
+impl Neighbourhood {
+    // Only the hexagonal neighbourhood looks different from odd rows.
+    pub fn includes(&self, dx: i32, dy: i32, radius: i32, odd_row: bool) -> bool {
+        if dx == 0 && dy == 0 {
+            return false;
+        }
+
+        match self {
+            Neighbourhood::Moore => dx.abs() <= radius && dy.abs() <= radius,
+            Neighbourhood::VonNeumann => dx.abs() + dy.abs() <= radius,
+            Neighbourhood::Hexagonal => {
+                // The distance in cube coordinates, where each row's hexagons shift half a cell
+                // to the left of the row above, undoing the offset of odd rows.
+                let dq = dx - (odd_row as i32 + dy).div_euclid(2);
+
+                dq.abs() + dy.abs() + (dq + dy).abs() <= radius * 2
+            }
+        }
+    }
+
+    pub fn next(&self) -> Neighbourhood {
+        match self {
+            Neighbourhood::Moore => Neighbourhood::VonNeumann,
+            Neighbourhood::VonNeumann => Neighbourhood::Hexagonal,
+            Neighbourhood::Hexagonal => Neighbourhood::Moore,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Neighbourhood::Moore => "Moore",
+            Neighbourhood::VonNeumann => "von Neumann",
+            Neighbourhood::Hexagonal => "hexagonal",
+        }
+    }
+}
+
+// Bounded worlds treat everything past the edges as dead, so a glider flying into a corner
+// piles up into a block there, where on a torus it would carry on round. LiveEdge is bounded too,
+// but everything past the edges counts as alive for good. Edge cells then have three or more live
+// neighbours to start with, so under most rules activity grows inward from the edges.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Topology {
+    Bounded,
+    WrapX,
+    WrapY,
+    Torus,
+    LiveEdge,
+}
+
+impl Topology {
+    pub fn wraps_x(&self) -> bool {
+        matches!(self, Topology::WrapX | Topology::Torus)
+    }
+
+    pub fn wraps_y(&self) -> bool {
+        matches!(self, Topology::WrapY | Topology::Torus)
+    }
+
+    pub fn next(&self) -> Topology {
+        match self {
+            Topology::Bounded => Topology::WrapX,
+            Topology::WrapX => Topology::WrapY,
+            Topology::WrapY => Topology::Torus,
+            Topology::Torus => Topology::LiveEdge,
+            Topology::LiveEdge => Topology::Bounded,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Topology::Bounded => "bounded",
+            Topology::WrapX => "wrap x",
+            Topology::WrapY => "wrap y",
+            Topology::Torus => "torus",
+            Topology::LiveEdge => "live edge",
+        }
+    }
+
+    pub fn wrap(&self, coordinate: &Vector, min: &Vector, size: &Vector) -> Vector {
+        Vector {
+            x: if self.wraps_x() { min.x + (coordinate.x - min.x).rem_euclid(size.x) } else { coordinate.x },
+            y: if self.wraps_y() { min.y + (coordinate.y - min.y).rem_euclid(size.y) } else { coordinate.y },
+        }
+    }
+
+    pub fn delta(&self, dx: i32, dy: i32, size: &Vector) -> (i32, i32) {
+        let shortest = |delta: i32, length: i32| {
+            let delta = delta.rem_euclid(length);
+
+            if delta * 2 > length { delta - length } else { delta }
+        };
+
+        (
+            if self.wraps_x() { shortest(dx, size.x) } else { dx },
+            if self.wraps_y() { shortest(dy, size.y) } else { dy },
+        )
+    }
+}
+
+// Ways of seeding a random world from a smaller random fundamental domain. Each one stays
+// seamless on a torus: the two mirror lines of Mirror are the middle and the wrapped edge, Rotate
+// pairs cells through the centre and the seam alike, and Tile repeats a domain that divides the
+// world exactly, so the last tile runs straight on into the first.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Symmetry {
+    Mirror,
+    Rotate,
+    Tile(i32),
+}
+
+impl Symmetry {
+    pub fn fits(&self, size: &Vector) -> bool {
+        match self {
+            Symmetry::Tile(tiles) => size.x % tiles == 0 && size.y % tiles == 0,
+            _ => true,
+        }
+    }
+
+    // The cell of the fundamental domain, indexed like `World::cells`, that (x, y) copies.
+    fn source(&self, x: i32, y: i32, size: &Vector) -> (usize, usize) {
+        let (width, height) = (size.x, size.y);
+
+        let (x, y) = match self {
+            Symmetry::Mirror => (x.min(width - 1 - x), y.min(height - 1 - y)),
+            Symmetry::Rotate => (x, y).min((width - 1 - x, height - 1 - y)),
+            Symmetry::Tile(tiles) => (x % (width / tiles), y % (height / tiles)),
+        };
+
+        (x as usize, y as usize)
+    }
+}
+
+impl FromStr for Symmetry {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Symmetry, String> {
+        match value.split_once(':') {
+            None if value == "mirror" => Ok(Symmetry::Mirror),
+            None if value == "rotate" => Ok(Symmetry::Rotate),
+            Some(("tile", tiles)) => match tiles.parse() {
+                Ok(tiles) if tiles > 0 => Ok(Symmetry::Tile(tiles)),
+                _ => Err(format!("Invalid tile count: {}", tiles)),
+            },
+            _ => Err(format!("Unknown symmetry: {} (use mirror, rotate or tile:N)", value)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum UpdateScheme {
+    Synchronous,
+    Sequential,
+}
+
+impl UpdateScheme {
+    pub fn next(&self) -> UpdateScheme {
+        match self {
+            UpdateScheme::Synchronous => UpdateScheme::Sequential,
+            UpdateScheme::Sequential => UpdateScheme::Synchronous,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            UpdateScheme::Synchronous => "synchronous",
+            UpdateScheme::Sequential => "sequential",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vector {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Vector {
+    pub fn out_of_bounds(&self, min: &Vector, max: &Vector) -> bool {
+        self.x < min.x || self.y < min.y || self.x >= max.x || self.y >= max.y
+    }
+}
+
+pub trait CellState: Copy + PartialEq + Hash {
+    fn dead() -> Self;
+    fn live() -> Self;
+    fn is_live_for_counting(&self) -> bool;
+    fn next(&self, neighbour_sum: u32, rule: &Rule) -> Self;
+
+    // Generations left before a dead cell can be born again. See `cooldown::Cooling`.
+    fn cooldown(&self) -> u32 {
+        0
+    }
+}
+
+impl CellState for bool {
+    fn dead() -> bool {
+        false
+    }
+
+    fn live() -> bool {
+        true
+    }
+
+    fn is_live_for_counting(&self) -> bool {
+        *self
+    }
+
+    fn next(&self, neighbour_sum: u32, rule: &Rule) -> bool {
+        rule.next_state(*self, neighbour_sum)
+    }
+}
+
+pub struct Cell<S = bool> {
+    pub state: S,
+    pub coordinate: Vector,
+}
+
+impl<S: CellState> Cell<S> {
+    pub fn is_alive(&self) -> bool {
+        self.state.is_live_for_counting()
+    }
+
+    pub fn determine_next_state(&self, world: &World<S>) -> S {
+        let sum = world.neighbour_sum_at(&self.coordinate) + world.background_sum_at(&self.coordinate);
+
+        self.state.next(sum, &world.rule)
+    }
+
+    pub fn neighbour_sum(&self, world: &World<S>) -> u32 {
+        let mut neighbour_sum = 0;
+        let max = world.max();
+
+        for offset in world.offsets_from(self.coordinate.y) {
+            let lookup_coordinate = world.topology.wrap(
+                &Vector {
+                    x: self.coordinate.x + offset.x,
+                    y: self.coordinate.y + offset.y,
+                },
+                &world.min,
+                &world.size,
+            );
+
+            if lookup_coordinate.out_of_bounds(&world.min, &max) {
+                continue;
+            }
+
+            if !world.cell_at(&lookup_coordinate).is_alive() {
+                continue;
+            }
+
+            neighbour_sum += offset.weight;
+        }
+
+        neighbour_sum
+    }
+}
+
+struct Offset {
+    x: i32,
+    y: i32,
+    weight: u32,
+}
+
+// How a run measured by `World::run_until_settled` ended. A still life settles with period 1.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Settling {
+    Settled { generation: u64, period: u64 },
+    Unsettled { generations: u64 },
+}
+
+// One cell's turn in a tick: the sum its next state was decided from, and that decision.
+#[derive(Clone, Copy)]
+pub struct Evaluation<S = bool> {
+    pub x: usize,
+    pub y: usize,
+    pub neighbour_sum: u32,
+    pub state: S,
+    pub next_state: S,
+}
+
+// A tick taken one cell at a time in reading order, see `World::step_walk`.
+pub struct TickWalk<S = bool> {
+    next: usize,
+    changes: Vec<(usize, usize, S)>,
+}
+
+impl<S> TickWalk<S> {
+    pub fn new() -> TickWalk<S> {
+        TickWalk { next: 0, changes: Vec::new() }
+    }
+
+    // How many cells have been evaluated so far.
+    pub fn evaluated(&self) -> usize {
+        self.next
+    }
+}
+
+impl<S> Default for TickWalk<S> {
+    fn default() -> TickWalk<S> {
+        TickWalk::new()
+    }
+}
+
+pub struct World<S = bool> {
+    pub frames: u64,
+    pub min: Vector,
+    pub size: Vector,
+    pub cells: Vec<Vec<Cell<S>>>,
+    pub changed: bool,
+    pub population: u64,
+    pub population_history: VecDeque<u64>,
+    pub neighbourhood: Neighbourhood,
+    pub topology: Topology,
+    pub radius: i32,
+    pub rule: Rule,
+    pub weights: Weights,
+    offsets: Vec<Offset>,
+    // The offsets for odd rows, where they differ from even ones, as in a hexagonal neighbourhood.
+    odd_row_offsets: Option<Vec<Offset>>,
+    neighbour_sums: Vec<Vec<u32>>,
+    // What the cells beyond bounded edges count as. A B0 rule brings the infinite background to
+    // life, so it is evolved alongside the grid instead of being taken as always dead.
+    background: S,
+    edge_sums: Vec<Vec<u32>>,
+    pub gravity: bool,
+    pub include_centre: bool,
+    pub update_scheme: UpdateScheme,
+    pub seed: Option<u64>,
+    state_history: VecDeque<u64>,
+    // Indexed [x][y] like `cells`; cells outside the mask stay dead and are never evaluated.
+    // None lets every cell take part.
+    mask: Option<Vec<Vec<bool>>>,
+    pub period: Option<u64>,
+    // The [x][y] indices of every cell that came to life or died since the last tick began,
+    // edits in between included.
+    flips: Vec<(usize, usize)>,
+    // Written to the header of saved patterns, and read back from loaded ones.
+    pub metadata: Metadata,
+}
+
+// Crops or pads `mask` to `size`, with padding falling outside it.
+fn fit_mask(mask: &[Vec<bool>], size: &Vector) -> Vec<Vec<bool>> {
+    (0..size.x as usize)
+        .map(|x| (0..size.y as usize).map(|y| mask.get(x).and_then(|column| column.get(y)) == Some(&true)).collect())
+        .collect()
+}
+
+// Indexed [x][y] like `World::cells`, drawing one random number per cell in that order. Seeded
+// worlds rely on that order, and on drawing floats rather than anything sized like usize, to
+// come out the same on every platform.
+pub fn seed_grid(size: &Vector, density: f64, rng: &mut impl Rng) -> Vec<Vec<bool>> {
+    (0..size.x)
+        .map(|_| (0..size.y).map(|_| rng.gen_range(0.0..1.0) < density).collect())
+        .collect()
+}
+
+impl World {
+    pub fn new(size: &Vector, life_chance: f64, rng: &mut impl Rng) -> World {
+        World::with_min(&DEFAULT_WORLD_MIN, size, life_chance, rng)
+    }
+
+    // Nothing comes alive at a density of 0, so the rng's values never matter.
+    pub fn empty(size: &Vector) -> World {
+        World::new(size, 0.0, &mut StepRng::new(0, 0))
+    }
+
+    pub fn from_pattern(pattern: &Pattern, size: &Vector) -> World {
+        let centred = Vector {
+            x: (size.x - pattern.size.x) / 2,
+            y: (size.y - pattern.size.y) / 2,
+        };
+
+        World::from_pattern_at(pattern, size, &centred)
+    }
+
+    // Places the pattern's top left corner at `at`, clipping whatever falls outside the world.
+    pub fn from_pattern_at(pattern: &Pattern, size: &Vector, at: &Vector) -> World {
+        let mut world = World::empty(size);
+        world.stamp(pattern, at);
+
+        if let Some(rule) = &pattern.rule {
+            world.set_rule(rule.clone());
+        }
+
+        world.metadata = pattern.metadata.clone();
+        world
+    }
+}
+
+impl<S: CellState> World<S> {
+    // Panics unless both sides of `size` are positive.
+    pub fn with_min(min: &Vector, size: &Vector, life_chance: f64, rng: &mut impl Rng) -> World<S> {
+        assert!(size.x > 0 && size.y > 0, "World size must be positive, got {}x{}", size.x, size.y);
+
+        let mut cells = Vec::new();
+
+        for (x, column) in seed_grid(size, life_chance, rng).into_iter().enumerate() {
+            let mut row = Vec::new();
+
+            for (y, alive) in column.into_iter().enumerate() {
+                row.push(Cell {
+                    coordinate: Vector { x: min.x + x as i32, y: min.y + y as i32 },
+                    state: if alive { S::live() } else { S::dead() },
+                });
+            }
+
+            cells.push(row);
+        }
+
+        let population = cells.iter().flatten().filter(|cell| cell.is_alive()).count() as u64;
+
+        let mut world = World {
+            frames: 0,
+            cells,
+            min: *min,
+            size: *size,
+            changed: false,
+            population,
+            population_history: VecDeque::from([population]),
+            neighbourhood: Neighbourhood::Moore,
+            topology: Topology::Bounded,
+            radius: 1,
+            rule: Rule::conway(),
+            weights: Weights::uniform(1),
+            offsets: Vec::new(),
+            odd_row_offsets: None,
+            neighbour_sums: Vec::new(),
+            background: S::dead(),
+            edge_sums: Vec::new(),
+            gravity: false,
+            include_centre: false,
+            update_scheme: UpdateScheme::Synchronous,
+            seed: None,
+            state_history: VecDeque::new(),
+            mask: None,
+            period: None,
+            flips: Vec::new(),
+            metadata: Metadata::default(),
+        };
+
+        world.update_offsets();
+        world.update_period(false);
+        world
+    }
+
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+        self.state_history.clear();
+    }
+
+    pub fn set_neighbourhood(&mut self, neighbourhood: Neighbourhood) {
+        self.neighbourhood = neighbourhood;
+        self.update_offsets();
+    }
+
+    pub fn set_update_scheme(&mut self, update_scheme: UpdateScheme) {
+        self.update_scheme = update_scheme;
+        self.state_history.clear();
+    }
+
+    // Counts each cell's own state in its sum, once whatever the weights say. The rule is applied
+    // to that sum unchanged, so births read the same but survivals are one higher than usual:
+    // Life with the centre counted is written B3/S34.
+    pub fn set_include_centre(&mut self, include_centre: bool) {
+        self.include_centre = include_centre;
+        self.update_offsets();
+    }
+
+    pub fn set_gravity(&mut self, gravity: bool) {
+        self.gravity = gravity;
+        self.state_history.clear();
+    }
+
+    // Recounts every sum straight away, including the edge sums a live background adds, so the
+    // next tick already sees neighbours across a newly wrapped seam (or stops seeing them). The
+    // old period no longer holds either; it is found afresh from here.
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+        self.state_history.clear();
+        self.period = None;
+        self.recount_neighbour_sums();
+    }
+
+    pub fn set_radius(&mut self, radius: i32) {
+        self.radius = radius;
+        self.weights = Weights::uniform(radius);
+        self.update_offsets();
+    }
+
+    pub fn set_weights(&mut self, weights: Weights) {
+        self.radius = weights.radius;
+        self.weights = weights;
+        self.update_offsets();
+    }
+
+    pub fn set_larger_than_life(&mut self, rule: &LargerThanLife) {
+        self.radius = rule.radius;
+        self.weights = Weights::uniform(rule.radius);
+        self.neighbourhood = rule.neighbourhood;
+        self.include_centre = rule.include_centre;
+        self.set_rule(rule.rule.clone());
+        self.update_offsets();
+    }
+
+    fn update_offsets(&mut self) {
+        self.state_history.clear();
+        self.offsets = self.row_offsets(false);
+        self.odd_row_offsets = match self.neighbourhood {
+            Neighbourhood::Hexagonal => Some(self.row_offsets(true)),
+            _ => None,
+        };
+
+        self.recount_neighbour_sums();
+    }
+
+    fn row_offsets(&self, odd_row: bool) -> Vec<Offset> {
+        let mut offsets = Vec::new();
+
+        for x in -self.radius..=self.radius {
+            for y in -self.radius..=self.radius {
+                let weight = self.weights.get(x, y);
+
+                if weight == 0 || !self.neighbourhood.includes(x, y, self.radius, odd_row) {
+                    continue;
+                }
+
+                offsets.push(Offset { x, y, weight });
+            }
+        }
+
+        if self.include_centre {
+            offsets.push(Offset { x: 0, y: 0, weight: 1 });
+        }
+
+        offsets
+    }
+
+    fn is_odd_row(&self, y: i32) -> bool {
+        (y - self.min.y).rem_euclid(2) == 1
+    }
+
+    // The offsets to the neighbours of a cell on row `y`.
+    fn offsets_from(&self, y: i32) -> &[Offset] {
+        match &self.odd_row_offsets {
+            Some(odd_row_offsets) if self.is_odd_row(y) => odd_row_offsets,
+            _ => &self.offsets,
+        }
+    }
+
+    fn recount_neighbour_sums(&mut self) {
+        self.neighbour_sums = self.cells.iter()
+            .map(|column| column.iter().map(|cell| cell.neighbour_sum(self)).collect())
+            .collect();
+
+        self.edge_sums = self.cells.iter()
+            .map(|column| column.iter().map(|cell| self.edge_sum(&cell.coordinate)).collect())
+            .collect();
+    }
+
+    // The weight of the neighbours that fall past a bounded edge.
+    fn edge_sum(&self, coordinate: &Vector) -> u32 {
+        let max = self.max();
+
+        self.offsets_from(coordinate.y).iter()
+            .filter(|offset| {
+                let neighbour = Vector { x: coordinate.x + offset.x, y: coordinate.y + offset.y };
+
+                self.topology.wrap(&neighbour, &self.min, &self.size).out_of_bounds(&self.min, &max)
+            })
+            .map(|offset| offset.weight)
+            .sum()
+    }
+
+    fn background_sum_at(&self, coordinate: &Vector) -> u32 {
+        match self.edges_alive() {
+            true => self.edge_sums[(coordinate.x - self.min.x) as usize][(coordinate.y - self.min.y) as usize],
+            false => 0,
+        }
+    }
+
+    pub fn background_alive(&self) -> bool {
+        self.background.is_live_for_counting()
+    }
+
+    // Whether neighbours past a bounded edge count as alive, from a live background or a live edge.
+    fn edges_alive(&self) -> bool {
+        self.topology == Topology::LiveEdge || self.background_alive()
+    }
+
+    fn update_background(&mut self) {
+        let sum = match self.background.is_live_for_counting() {
+            true => self.offsets.iter().map(|offset| offset.weight).sum(),
+            false => 0,
+        };
+
+        self.background = self.background.next(sum, &self.rule);
+    }
+
+    // Each cell counting this one is found by stepping back along the offsets of its own row, so
+    // when odd rows have offsets of their own, each table only finds cells on rows it belongs to.
+    fn adjust_neighbour_sums(&mut self, x: usize, y: usize, alive: bool) {
+        let coordinate = self.cells[x][y].coordinate;
+        let max = self.max();
+        let tables = [(Some(&self.offsets), false), (self.odd_row_offsets.as_ref(), true)];
+
+        for (offsets, odd_row) in tables {
+            let Some(offsets) = offsets else {
+                continue;
+            };
+
+            for offset in offsets {
+                let neighbour = self.topology.wrap(
+                    &Vector {
+                        x: coordinate.x - offset.x,
+                        y: coordinate.y - offset.y,
+                    },
+                    &self.min,
+                    &self.size,
+                );
+
+                if neighbour.out_of_bounds(&self.min, &max) {
+                    continue;
+                }
+
+                if self.odd_row_offsets.is_some() && self.is_odd_row(neighbour.y) != odd_row {
+                    continue;
+                }
+
+                let sum = &mut self.neighbour_sums[(neighbour.x - self.min.x) as usize][(neighbour.y - self.min.y) as usize];
+
+                match alive {
+                    true => *sum += offset.weight,
+                    false => *sum -= offset.weight,
+                }
+            }
+        }
+    }
+
+    fn neighbour_sum_at(&self, coordinate: &Vector) -> u32 {
+        self.neighbour_sums[(coordinate.x - self.min.x) as usize][(coordinate.y - self.min.y) as usize]
+    }
+
+    pub fn set_alive(&mut self, x: usize, y: usize, alive: bool) {
+        self.set_state(x, y, if alive { S::live() } else { S::dead() });
+    }
+
+    pub fn set_state(&mut self, x: usize, y: usize, state: S) {
+        let cell = &mut self.cells[x][y];
+
+        if cell.state == state {
+            return;
+        }
+
+        self.write_state(x, y, state);
+        self.state_history.clear();
+        self.period = None;
+    }
+
+    // Reseeds just the cells of `region` that lie inside the world, leaving everything else
+    // (including `frames`) as it was.
+    #[cfg(feature = "std")]
+    pub fn randomize_region(&mut self, region: &Rect, density: f64, rng: &mut impl Rng) {
+        let right = min(region.right() as i32, self.size.x);
+        let bottom = min(region.bottom() as i32, self.size.y);
+
+        self.randomize_cells(region.x as i32..right, region.y as i32..bottom, density, rng);
+    }
+
+    // Reseeds the whole world in place, drawing in the same order as `seed_grid` so the same rng
+    // gives the same cells as a fresh world would.
+    pub fn randomize(&mut self, density: f64, rng: &mut impl Rng) {
+        self.randomize_cells(0..self.size.x, 0..self.size.y, density, rng);
+    }
+
+    // Draws the whole grid like `randomize` and then copies the fundamental domain over the rest,
+    // so the same seed always gives the same world. Panics unless `symmetry` fits the world.
+    pub fn randomize_symmetric(&mut self, density: f64, symmetry: Symmetry, rng: &mut impl Rng) {
+        assert!(symmetry.fits(&self.size), "Tiles must divide the {}x{} world exactly", self.size.x, self.size.y);
+
+        let grid = seed_grid(&self.size, density, rng);
+
+        for x in 0..self.size.x {
+            for y in 0..self.size.y {
+                let (source_x, source_y) = symmetry.source(x, y, &self.size);
+                let state = if grid[source_x][source_y] { S::live() } else { S::dead() };
+                self.write_state(x as usize, y as usize, state);
+            }
+        }
+
+        self.state_history.clear();
+        self.period = None;
+    }
+
+    fn randomize_cells(&mut self, columns: Range<i32>, rows: Range<i32>, density: f64, rng: &mut impl Rng) {
+        for x in columns {
+            for y in rows.clone() {
+                let alive = rng.gen_range(0.0..1.0) < density;
+                self.write_state(x as usize, y as usize, if alive { S::live() } else { S::dead() });
+            }
+        }
+
+        self.state_history.clear();
+        self.period = None;
+    }
+
+    fn write_state(&mut self, x: usize, y: usize, state: S) {
+        let state = if self.in_mask(x, y) { state } else { S::dead() };
+        let cell = &mut self.cells[x][y];
+        let was_alive = cell.is_alive();
+        cell.state = state;
+
+        if was_alive != cell.is_alive() {
+            self.flips.push((x, y));
+        }
+
+        match (was_alive, cell.is_alive()) {
+            (false, true) => {
+                self.population += 1;
+                self.adjust_neighbour_sums(x, y, true);
+            }
+            (true, false) => {
+                self.population -= 1;
+                self.adjust_neighbour_sums(x, y, false);
+            }
+            _ => {}
+        }
+    }
+
+    // Sets the `brush` by `brush` square centred on `centre`, or just up and left of centre for an
+    // even brush, skipping whatever falls outside the world.
+    pub fn paint(&mut self, centre: &Vector, brush: i32, alive: bool) {
+        let from = Vector { x: centre.x - (brush - 1) / 2, y: centre.y - (brush - 1) / 2 };
+
+        for x in max(from.x, 0)..min(from.x + brush, self.size.x) {
+            for y in max(from.y, 0)..min(from.y + brush, self.size.y) {
+                self.set_alive(x as usize, y as usize, alive);
+            }
+        }
+    }
+
+    pub fn paint_line(&mut self, from: &Vector, to: &Vector, brush: i32, alive: bool) {
+        let dx = (to.x - from.x).abs();
+        let dy = -(to.y - from.y).abs();
+        let step_x = if from.x < to.x { 1 } else { -1 };
+        let step_y = if from.y < to.y { 1 } else { -1 };
+
+        let mut point = *from;
+        let mut error = dx + dy;
+
+        loop {
+            self.paint(&point, brush, alive);
+
+            if point.x == to.x && point.y == to.y {
+                break;
+            }
+
+            let doubled_error = error * 2;
+
+            if doubled_error >= dy {
+                error += dy;
+                point.x += step_x;
+            }
+
+            if doubled_error <= dx {
+                error += dx;
+                point.y += step_y;
+            }
+        }
+    }
+
+    // Cells that land outside the world are dropped, or wrapped onto it on wrapped axes.
+    pub fn stamp(&mut self, pattern: &Pattern, at: &Vector) {
+        for cell in &pattern.cells {
+            let position = self.topology.wrap(
+                &Vector { x: at.x + cell.x, y: at.y + cell.y },
+                &DEFAULT_WORLD_MIN,
+                &self.size,
+            );
+
+            if !position.out_of_bounds(&DEFAULT_WORLD_MIN, &self.size) {
+                self.set_alive(position.x as usize, position.y as usize, true);
+            }
+        }
+    }
+
+    pub fn toggle_cell(&mut self, x: usize, y: usize) {
+        self.set_alive(x, y, !self.cells[x][y].is_alive());
+    }
+
+    // Flips `flips` cells picked at random, possibly the same one more than once.
+    pub fn mutate(&mut self, flips: u32, rng: &mut impl Rng) {
+        for _ in 0..flips {
+            let x = rng.gen_range(0..self.size.x) as usize;
+            let y = rng.gen_range(0..self.size.y) as usize;
+
+            self.toggle_cell(x, y);
+        }
+    }
+
+    // Lets loosely connected live cells fall one row into empty space, working up from the
+    // bottom so nothing falls twice. Returns whether anything moved.
+    fn apply_gravity(&mut self) -> bool {
+        let mut moved = false;
+
+        for y in (0..(self.size.y as usize).saturating_sub(1)).rev() {
+            for x in 0..self.size.x as usize {
+                let cell = &self.cells[x][y];
+
+                if !cell.is_alive() || self.cells[x][y + 1].is_alive() || !self.in_mask(x, y + 1) {
+                    continue;
+                }
+
+                if self.neighbour_sum_at(&cell.coordinate) >= GRAVITY_NEIGHBOUR_THRESHOLD {
+                    continue;
+                }
+
+                let state = cell.state;
+                self.write_state(x, y, S::dead());
+                self.write_state(x, y + 1, state);
+                moved = true;
+            }
+        }
+
+        moved
+    }
+
+    // Grows or crops the world on its right and bottom, so every cell that stays keeps its place
+    // from the top left corner. Panics unless both sides of `new_size` are positive.
+    pub fn resize(&mut self, new_size: &Vector) {
+        assert!(new_size.x > 0 && new_size.y > 0, "World size must be positive, got {}x{}", new_size.x, new_size.y);
+
+        let max = self.max();
+        let mut cells = Vec::new();
+
+        for x in 0..new_size.x {
+            let mut row = Vec::new();
+
+            for y in 0..new_size.y {
+                let coordinate = Vector { x: self.min.x + x, y: self.min.y + y };
+                let state = match coordinate.out_of_bounds(&self.min, &max) {
+                    true => S::dead(),
+                    false => self.cell_at(&coordinate).state,
+                };
+
+                row.push(Cell { state, coordinate });
+            }
+
+            cells.push(row);
+        }
+
+        if let Some(mask) = &mut self.mask {
+            *mask = fit_mask(mask, new_size);
+        }
+
+        self.cells = cells;
+        self.size = *new_size;
+        self.population = self.cells.iter().flatten().filter(|cell| cell.is_alive()).count() as u64;
+        self.state_history.clear();
+        self.period = None;
+        self.recount_neighbour_sums();
+    }
+
+    pub fn in_mask(&self, x: usize, y: usize) -> bool {
+        self.mask.as_ref().is_none_or(|mask| mask[x][y])
+    }
+
+    // Limits the world to `mask` (indexed [x][y], missing cells counting as outside), killing
+    // whatever lies beyond it. None brings every cell back into play.
+    pub fn set_mask(&mut self, mask: Option<Vec<Vec<bool>>>) {
+        self.mask = mask.map(|mask| fit_mask(&mask, &self.size));
+
+        for x in 0..self.size.x as usize {
+            for y in 0..self.size.y as usize {
+                if !self.in_mask(x, y) {
+                    self.write_state(x, y, S::dead());
+                }
+            }
+        }
+
+        self.state_history.clear();
+        self.period = None;
+    }
+
+    // Masks the world to the live cells of `shape`, laid over it from the top left corner.
+    pub fn set_mask_shape(&mut self, shape: &Pattern) {
+        let mut mask = vec![vec![false; self.size.y as usize]; self.size.x as usize];
+
+        for cell in &shape.cells {
+            if cell.x >= 0 && cell.y >= 0 && cell.x < self.size.x && cell.y < self.size.y {
+                mask[cell.x as usize][cell.y as usize] = true;
+            }
+        }
+
+        self.set_mask(Some(mask));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size.x <= 0 || self.size.y <= 0
+    }
+
+    pub fn alive_cells(&self) -> impl Iterator<Item = Vector> + '_ {
+        self.cells.iter()
+            .flatten()
+            .filter(|cell| cell.is_alive())
+            .map(|cell| cell.coordinate)
+    }
+
+    // The average position of the live cells, rounded down, or None when there are none. It
+    // takes no account of wrapping, so a structure straddling a seam averages to the middle.
+    pub fn centroid(&self) -> Option<Vector> {
+        if self.population == 0 {
+            return None;
+        }
+
+        let (x, y) = self.alive_cells().fold((0, 0), |(x, y), cell| (x + cell.x as i64, y + cell.y as i64));
+        let population = self.population as i64;
+
+        Some(Vector {
+            x: x.div_euclid(population) as i32,
+            y: y.div_euclid(population) as i32,
+        })
+    }
+
+    // Nothing is alive, and nothing can be born from nothing: no B0 rule, live edge, or live
+    // background left over from a B0 rule. Such a world stays empty for good.
+    pub fn is_extinct(&self) -> bool {
+        self.population == 0 && !self.rule.next_state(false, 0) && !self.edges_alive()
+    }
+
+    // The mean population, rounded, once it has stayed within EQUILIBRIUM_BAND_PERCENT of it for
+    // the whole population history while the cells kept changing without repeating. The drift
+    // check keeps out soups that are still slowly dying down. Unlike `period` this is only a
+    // statistical guess: a soup that boils forever counts, but so does a lone glider.
+    pub fn equilibrium(&self) -> Option<u64> {
+        if !self.changed || self.period.is_some() || self.population_history.len() < POPULATION_HISTORY_LENGTH {
+            return None;
+        }
+
+        let mean = |populations: &[u64]| {
+            let generations = populations.len() as u64;
+            (populations.iter().sum::<u64>() + generations / 2) / generations
+        };
+
+        let history: Vec<u64> = self.population_history.iter().copied().collect();
+        let (earlier, later) = history.split_at(history.len() / 2);
+        let overall = mean(&history);
+
+        let steady = history.iter().all(|population| population.abs_diff(overall) * 100 <= overall * EQUILIBRIUM_BAND_PERCENT);
+        let drifting = mean(earlier).abs_diff(mean(later)) * 100 > overall * EQUILIBRIUM_DRIFT_PERCENT;
+
+        (steady && !drifting).then_some(overall)
+    }
+
+    // One line covering the generation, size, edges, neighbourhood and rule, for logs and the info bar.
+    pub fn describe(&self) -> String {
+        format!(
+            "Frame: {} // Population: {} // {}x{} {} // {} r{} // {}{}{}",
+            self.frames,
+            self.population,
+            self.size.x,
+            self.size.y,
+            self.topology.name(),
+            self.neighbourhood.name(),
+            self.radius,
+            self.rule,
+            if self.include_centre { " + centre" } else { "" },
+            if self.background_alive() { " // live background" } else { "" }
+        )
+    }
+
+    pub fn max(&self) -> Vector {
+        Vector {
+            x: self.min.x + self.size.x,
+            y: self.min.y + self.size.y,
+        }
+    }
+
+    pub fn cell_at(&self, coordinate: &Vector) -> &Cell<S> {
+        &self.cells[(coordinate.x - self.min.x) as usize][(coordinate.y - self.min.y) as usize]
+    }
+
+    pub fn tick(&mut self) {
+        // An extinct world has no cell worth looking at, once a tick has gone by without change
+        // so any cooling cells are done counting down.
+        let stays_empty = self.is_extinct() && !self.changed;
+        self.flips.clear();
+
+        let did_change = match (stays_empty, self.update_scheme) {
+            (true, _) => false,
+            (false, UpdateScheme::Synchronous) => self.update_synchronously(),
+            (false, UpdateScheme::Sequential) => self.update_sequentially(),
+        };
+
+        self.finish_tick(did_change);
+    }
+
+    // Ticks until the period detector fires or the world reaches generation `cap`. A settled
+    // world entered its cycle `period` generations before the repeat was spotted, so that is the
+    // generation reported. Only periods within the detector's history are caught.
+    pub fn run_until_settled(&mut self, cap: u64) -> Settling {
+        while self.frames < cap {
+            self.tick();
+
+            if let Some(period) = self.period {
+                return Settling::Settled { generation: self.frames - period, period };
+            }
+        }
+
+        Settling::Unsettled { generations: self.frames }
+    }
+
+    // Evaluates the next cell of `walk`, finishing the tick exactly as `tick` would once the last
+    // cell is done. Synchronous updates hold every decision back until then, so each cell is
+    // still decided from the previous generation; sequential ones are written straight away.
+    // Returns None once the walk is over.
+    pub fn step_walk(&mut self, walk: &mut TickWalk<S>) -> Option<Evaluation<S>> {
+        let width = self.size.x as usize;
+        let total = width * self.size.y as usize;
+
+        if walk.next >= total {
+            return None;
+        }
+
+        if walk.next == 0 {
+            self.flips.clear();
+        }
+
+        let (x, y) = (walk.next % width, walk.next / width);
+        let evaluation = match self.in_mask(x, y) {
+            true => self.evaluate(x, y),
+            false => Evaluation { x, y, neighbour_sum: 0, state: S::dead(), next_state: S::dead() },
+        };
+        walk.next += 1;
+
+        if evaluation.next_state != evaluation.state {
+            walk.changes.push((x, y, evaluation.next_state));
+
+            if self.update_scheme == UpdateScheme::Sequential {
+                self.write_state(x, y, evaluation.next_state);
+            }
+        }
+
+        if walk.next == total {
+            if self.update_scheme == UpdateScheme::Synchronous {
+                for &(x, y, state) in &walk.changes {
+                    self.write_state(x, y, state);
+                }
+            }
+
+            self.finish_tick(!walk.changes.is_empty());
+        }
+
+        Some(evaluation)
+    }
+
+    pub fn evaluate(&self, x: usize, y: usize) -> Evaluation<S> {
+        let cell = &self.cells[x][y];
+
+        Evaluation {
+            x,
+            y,
+            neighbour_sum: self.neighbour_sum_at(&cell.coordinate) + self.background_sum_at(&cell.coordinate),
+            state: cell.state,
+            next_state: cell.determine_next_state(self),
+        }
+    }
+
+    fn finish_tick(&mut self, mut did_change: bool) {
+        let background = self.background;
+        self.update_background();
+
+        if self.gravity && self.population > 0 {
+            did_change |= self.apply_gravity();
+        }
+
+        self.frames += 1;
+        self.changed = did_change;
+
+        if self.population_history.len() == POPULATION_HISTORY_LENGTH {
+            self.population_history.pop_front();
+        }
+
+        self.population_history.push_back(self.population);
+        self.update_period(!did_change && background == self.background);
+    }
+
+    fn update_synchronously(&mut self) -> bool {
+        let mut new_states = Vec::new();
+
+        for x in 0..self.size.x {
+            for y in 0..self.size.y {
+                if !self.in_mask(x as usize, y as usize) {
+                    continue;
+                }
+
+                let cell = &self.cells[x as usize][y as usize];
+
+                let next_state = cell.determine_next_state(self);
+
+                if next_state == cell.state {
+                    continue;
+                }
+
+                new_states.push((
+                    x as usize,
+                    y as usize,
+                    next_state
+                ));
+            }
+        }
+
+        let did_change = !new_states.is_empty();
+
+        for (x, y, state) in new_states {
+            self.write_state(x, y, state);
+        }
+
+        did_change
+    }
+
+    // Updates cells one at a time in reading order, so each cell sees the new states of the
+    // cells before it.
+    fn update_sequentially(&mut self) -> bool {
+        let mut did_change = false;
+
+        for y in 0..self.size.y as usize {
+            for x in 0..self.size.x as usize {
+                if !self.in_mask(x, y) {
+                    continue;
+                }
+
+                let cell = &self.cells[x][y];
+                let next_state = cell.determine_next_state(self);
+
+                if next_state != cell.state {
+                    self.write_state(x, y, next_state);
+                    did_change = true;
+                }
+            }
+        }
+
+        did_change
+    }
+
+    // A world that didn't change this tick hashes the same as last time, so big empty or still
+    // worlds needn't be hashed all over again.
+    fn update_period(&mut self, unchanged: bool) {
+        let hash = match (unchanged, self.state_history.back()) {
+            (true, Some(previous)) => *previous,
+            _ => self.state_hash(),
+        };
+
+        self.period = self.state_history.iter()
+            .rev()
+            .position(|previous| *previous == hash)
+            .map(|generations_ago| generations_ago as u64 + 1);
+
+        if self.state_history.len() == STATE_HISTORY_LENGTH {
+            self.state_history.pop_front();
+        }
+
+        self.state_history.push_back(hash);
+    }
+
+    fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::default();
+
+        self.background.hash(&mut hasher);
+
+        for cell in self.cells.iter().flatten() {
+            cell.state.hash(&mut hasher);
+        }
+
+        hasher.finish()
+    }
+
+    pub fn snapshot(&self) -> Vec<Vec<bool>> {
+        self.cells.iter()
+            .map(|row| row.iter().map(|cell| cell.is_alive()).collect())
+            .collect()
+    }
+
+}
+
+// Terminal rendering, which needs ratatui and so std.
+#[cfg(feature = "std")]
+impl<S: CellState> World<S> {
+    pub fn draw_world(&self, textured: bool) -> Text<'static> {
+        self.render_cells(|x, y, cell| match cell.is_alive() {
+            true => ('#', Style::default()),
+            false if cell.state.cooldown() > 0 => ('+', Style::default().red()),
+            false if textured && (x + y) % 2 == 0 => ('·', Style::default().dark_gray()),
+            false => (' ', Style::default()),
+        })
+    }
+
+    pub fn draw_zoomed(&self, zoom: i32) -> Text<'static> {
+        if self.is_empty() {
+            return Text::from(EMPTY_WORLD_MESSAGE);
+        }
+
+        let mut lines = Vec::new();
+
+        for block_y in (0..self.size.y).step_by(zoom as usize) {
+            let mut line = String::new();
+
+            for block_x in (0..self.size.x).step_by(zoom as usize) {
+                let mut cells = 0;
+                let mut alive = 0;
+
+                for x in block_x..min(block_x + zoom, self.size.x) {
+                    for y in block_y..min(block_y + zoom, self.size.y) {
+                        cells += 1;
+                        alive += self.cells[x as usize][y as usize].is_alive() as i32;
+                    }
+                }
+
+                line.push(match alive * 4 / cells {
+                    _ if alive == 0 => ' ',
+                    0 => '░',
+                    1 => '▒',
+                    2 | 3 => '▓',
+                    _ => '█',
+                });
+            }
+
+            lines.push(Line::from(line));
+        }
+
+        Text::from(lines)
+    }
+
+    // Each character shows a cell and its right-hand neighbour as left and right half blocks.
+    pub fn draw_half_width(&self) -> Text<'static> {
+        if self.is_empty() {
+            return Text::from(EMPTY_WORLD_MESSAGE);
+        }
+
+        let alive = |x: i32, y: i32| x < self.size.x && self.cells[x as usize][y as usize].is_alive();
+        let lines: Vec<Line> = (0..self.size.y)
+            .map(|y| {
+                (0..self.size.x)
+                    .step_by(2)
+                    .map(|x| match (alive(x, y), alive(x + 1, y)) {
+                        (false, false) => ' ',
+                        (true, false) => '▌',
+                        (false, true) => '▐',
+                        (true, true) => '█',
+                    })
+                    .collect::<String>()
+                    .into()
+            })
+            .collect();
+
+        Text::from(lines)
+    }
+
+    pub fn draw_components(&self, colours: &ComponentColours) -> Text<'static> {
+        self.render_cells(|x, y, _| {
+            match colours.colour_at(x, y) {
+                Some(colour) => ('#', Style::default().fg(colour)),
+                None => (' ', Style::default()),
+            }
+        })
+    }
+
+    // Cells that died within the last `length` generations fade from blue to black.
+    pub fn draw_trails(&self, trails: &Trails, length: u64) -> Text<'static> {
+        self.render_cells(|x, y, cell| match (cell.is_alive(), trails.age(x, y, self.frames)) {
+            (true, _) => ('#', Style::default()),
+            (false, Some(age)) if age > 0 && age <= length => {
+                let level = (255 * (length + 1 - age) / (length + 1)) as u8;
+
+                ('#', Style::default().fg(Color::Rgb(0, level / 2, level)))
+            }
+            (false, _) => (' ', Style::default()),
+        })
+    }
+
+    // Shades every cell by the sum its next state is decided from, live cells drawn on top.
+    pub fn draw_neighbour_counts(&self) -> Text<'static> {
+        self.render_cells(|_, _, cell| {
+            let sum = self.neighbour_sum_at(&cell.coordinate) + self.background_sum_at(&cell.coordinate);
+            let colour = NEIGHBOUR_COUNT_COLOURS[min(sum as usize, NEIGHBOUR_COUNT_COLOURS.len() - 1)];
+            let glyph = if cell.is_alive() { '#' } else { ' ' };
+
+            let foreground = if sum == 0 { Color::White } else { Color::Black };
+
+            (glyph, Style::default().fg(foreground).bg(colour))
+        })
+    }
+
+    // Cells born in the last tick in green, and those that died in red.
+    pub fn draw_flips(&self) -> Text<'static> {
+        let mut flipped = vec![vec![false; self.size.y as usize]; self.size.x as usize];
+
+        for &(x, y) in &self.flips {
+            if let Some(cell) = flipped.get_mut(x).and_then(|column| column.get_mut(y)) {
+                *cell = true;
+            }
+        }
+
+        self.render_cells(|x, y, cell| match (cell.is_alive(), flipped[x as usize][y as usize]) {
+            (true, false) => ('#', Style::default()),
+            (true, true) => ('#', Style::default().green()),
+            (false, true) => ('#', Style::default().red()),
+            (false, false) => (' ', Style::default()),
+        })
+    }
+
+    pub fn draw_diff(&self, reference: &[Vec<bool>]) -> Text<'static> {
+        self.render_cells(|x, y, cell| {
+            let was_alive = reference.get(x as usize)
+                .and_then(|row| row.get(y as usize))
+                .copied()
+                .unwrap_or(false);
+
+            match (cell.is_alive(), was_alive) {
+                (true, true) => ('#', Style::default()),
+                (true, false) => ('#', Style::default().green()),
+                (false, true) => ('#', Style::default().red()),
+                (false, false) => (' ', Style::default()),
+            }
+        })
+    }
+
+    // The cells `walk` has already decided on show their new states in green and red, and the
+    // cell being evaluated is highlighted along with the neighbours it counts.
+    pub fn draw_walk(&self, walk: &TickWalk<S>, evaluation: &Evaluation<S>) -> Text<'static> {
+        let mut decided = vec![vec![None; self.size.y as usize]; self.size.x as usize];
+
+        for (x, y, state) in &walk.changes {
+            decided[*x][*y] = Some(state.is_live_for_counting());
+        }
+
+        let cursor = Vector { x: evaluation.x as i32, y: evaluation.y as i32 };
+
+        self.render_cells(|x, y, cell| {
+            let (dx, dy) = self.topology.delta(x - cursor.x, y - cursor.y, &self.size);
+            let counted = self.neighbourhood.includes(dx, dy, self.radius, cursor.y.rem_euclid(2) == 1);
+            let style = match (x == cursor.x && y == cursor.y, counted) {
+                (true, _) => Style::default().reversed(),
+                (false, true) => Style::default().on_dark_gray(),
+                (false, false) => Style::default(),
+            };
+
+            match (decided[x as usize][y as usize], cell.is_alive()) {
+                (Some(true), _) => ('#', style.green()),
+                (Some(false), _) => ('#', style.red()),
+                (None, true) => ('#', style),
+                (None, false) => (' ', style),
+            }
+        })
+    }
+
+    pub fn draw_neighbour_debug(&self, cursor: &Vector) -> Text<'static> {
+        self.render_cells(|x, y, cell| {
+            let (dx, dy) = self.topology.delta(x - cursor.x, y - cursor.y, &self.size);
+            let counted = self.neighbourhood.includes(dx, dy, self.radius, cursor.y.rem_euclid(2) == 1);
+
+            match (x == cursor.x && y == cursor.y, counted, cell.is_alive()) {
+                (true, _, true) => ('#', Style::default().reversed()),
+                (true, _, false) => (' ', Style::default().reversed()),
+                (false, true, true) => ('#', Style::default().yellow().on_dark_gray()),
+                (false, true, false) => (' ', Style::default().on_dark_gray()),
+                (false, false, true) => ('#', Style::default()),
+                (false, false, false) => (' ', Style::default()),
+            }
+        })
+    }
+
+    fn render_cells(&self, glyph_for: impl Fn(i32, i32, &Cell<S>) -> (char, Style)) -> Text<'static> {
+        if self.is_empty() {
+            return Text::from(EMPTY_WORLD_MESSAGE);
+        }
+
+        let mut lines = Vec::new();
+
+        for y in 0..self.size.y {
+            let mut spans = Vec::new();
+            let mut run = String::with_capacity(self.size.x as usize);
+            let mut run_style = Style::default();
+
+            for x in 0..self.size.x {
+                let (glyph, style) = match self.in_mask(x as usize, y as usize) {
+                    true => glyph_for(x, y, &self.cells[x as usize][y as usize]),
+                    false => ('░', Style::default().dark_gray()),
+                };
+
+                if style != run_style && !run.is_empty() {
+                    spans.push(Span::styled(std::mem::take(&mut run), run_style));
+                }
+
+                run_style = style;
+                run.push(glyph);
+            }
+
+            spans.push(Span::styled(run, run_style));
+            lines.push(Line::from(spans));
+        }
+
+        Text::from(lines)
+    }
+}
+
+// 64-bit FNV-1a, which is plenty for spotting repeated states and needs nothing from std.
+struct StateHasher(u64);
+
+impl Default for StateHasher {
+    fn default() -> StateHasher {
+        StateHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StateHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ *byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn seeded(size: &Vector, seed: u64) -> World {
+        World::new(size, 0.4, &mut StdRng::seed_from_u64(seed))
+    }
+
+    // What the incremental sums should be, counted from scratch.
+    fn recounted_sums(world: &World) -> (Vec<Vec<u32>>, Vec<Vec<u32>>) {
+        let neighbour_sums = world.cells.iter()
+            .map(|column| column.iter().map(|cell| cell.neighbour_sum(world)).collect())
+            .collect();
+        let edge_sums = world.cells.iter()
+            .map(|column| column.iter().map(|cell| world.edge_sum(&cell.coordinate)).collect())
+            .collect();
+
+        (neighbour_sums, edge_sums)
+    }
+
+    #[test]
+    fn out_of_bounds_includes_min_and_excludes_max() {
+        let (min, max) = (Vector { x: 0, y: 0 }, Vector { x: 5, y: 3 });
+        let outside = |x, y| Vector { x, y }.out_of_bounds(&min, &max);
+
+        // Along each edge: the last row or column in, one past it out.
+        for x in [0, 2, 4] {
+            assert!(!outside(x, 0) && !outside(x, 2));
+            assert!(outside(x, -1) && outside(x, 3));
+        }
+
+        for y in [0, 1, 2] {
+            assert!(!outside(0, y) && !outside(4, y));
+            assert!(outside(-1, y) && outside(5, y));
+        }
+
+        for (x, y) in [(0, 0), (4, 0), (0, 2), (4, 2)] {
+            assert!(!outside(x, y), "corner {},{}", x, y);
+        }
+
+        for (x, y) in [(-1, -1), (5, -1), (-1, 3), (5, 3), (5, 0), (0, 3)] {
+            assert!(outside(x, y), "past corner {},{}", x, y);
+        }
+    }
+
+    #[test]
+    fn out_of_bounds_respects_an_offset_min() {
+        let world: World = World::with_min(&Vector { x: -3, y: 2 }, &Vector { x: 4, y: 4 }, 0.0, &mut StdRng::seed_from_u64(0));
+        let outside = |x, y| Vector { x, y }.out_of_bounds(&world.min, &world.max());
+
+        assert!(!outside(-3, 2) && !outside(0, 5));
+        assert!(outside(-4, 2) && outside(-3, 1) && outside(1, 5) && outside(0, 6));
+    }
+
+    #[cfg(feature = "std")]
+    fn rendered(text: &Text) -> Vec<String> {
+        text.lines.iter().map(|line| line.spans.iter().map(|span| span.content.as_ref()).collect()).collect()
+    }
+
+    #[test]
+    fn one_cell_worlds_tick_and_draw() {
+        let mut world = World::empty(&Vector { x: 1, y: 1 });
+        world.set_alive(0, 0, true);
+
+        #[cfg(feature = "std")]
+        assert_eq!(rendered(&world.draw_world(false)), ["#"]);
+
+        world.tick();
+        assert!(world.is_extinct());
+
+        // On a torus the cell is all 8 of its own neighbours, too many to survive.
+        world.set_topology(Topology::Torus);
+        world.set_alive(0, 0, true);
+        assert_eq!(world.evaluate(0, 0).neighbour_sum, 8);
+        world.tick();
+        assert_eq!(world.population, 0);
+    }
+
+    #[test]
+    fn one_wide_worlds_shrink_from_both_ends() {
+        for size in [Vector { x: 1, y: 5 }, Vector { x: 5, y: 1 }] {
+            let mut world = World::empty(&size);
+
+            for i in 1..4 {
+                match size.x {
+                    1 => world.set_alive(0, i, true),
+                    _ => world.set_alive(i, 0, true),
+                }
+            }
+
+            world.tick();
+            assert_eq!(world.alive_cells().collect::<Vec<_>>(), [Vector { x: size.x / 2, y: size.y / 2 }]);
+
+            #[cfg(feature = "std")]
+            assert_eq!(rendered(&world.draw_world(false)).concat(), "  #  ");
+
+            world.tick();
+            assert!(world.is_extinct());
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn degenerate_sizes_draw_the_empty_world_message() {
+        let mut world = World::empty(&Vector { x: 3, y: 3 });
+        world.size = Vector { x: 0, y: 3 };
+
+        assert_eq!(rendered(&world.draw_world(true)), [EMPTY_WORLD_MESSAGE]);
+        assert_eq!(rendered(&world.draw_zoomed(2)), [EMPTY_WORLD_MESSAGE]);
+        assert_eq!(rendered(&world.draw_half_width()), [EMPTY_WORLD_MESSAGE]);
+    }
+
+    #[test]
+    #[should_panic(expected = "World size must be positive")]
+    fn zero_width_worlds_are_rejected() {
+        World::empty(&Vector { x: 0, y: 4 });
+    }
+
+    #[test]
+    #[should_panic(expected = "World size must be positive")]
+    fn negative_height_worlds_are_rejected() {
+        World::empty(&Vector { x: 4, y: -1 });
+    }
+
+    // One Conway generation of an [x][y] grid behind hard walls, counted cell by cell.
+    fn walled_step(grid: &[Vec<bool>]) -> Vec<Vec<bool>> {
+        let (width, height) = (grid.len() as i32, grid[0].len() as i32);
+        let alive = |x: i32, y: i32| x >= 0 && y >= 0 && x < width && y < height && grid[x as usize][y as usize];
+
+        (0..width)
+            .map(|x| {
+                (0..height)
+                    .map(|y| {
+                        let neighbours = (-1..=1)
+                            .flat_map(|dx| (-1..=1).map(move |dy| (dx, dy)))
+                            .filter(|&(dx, dy)| (dx, dy) != (0, 0) && alive(x + dx, y + dy))
+                            .count();
+
+                        neighbours == 3 || (neighbours == 2 && alive(x, y))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn a_glider_dies_into_a_block_against_a_bounded_corner() {
+        let size = Vector { x: 8, y: 8 };
+        let mut world = World::from_pattern_at(&Pattern::named("glider").unwrap(), &size, &Vector { x: 1, y: 1 });
+        let mut expected = world.snapshot();
+
+        // Walk the expected grid forward until it stops changing, then check the engine agrees.
+        let mut generations = 0;
+
+        loop {
+            let next = walled_step(&expected);
+
+            if next == expected {
+                break;
+            }
+
+            expected = next;
+            generations += 1;
+            world.tick();
+            assert_eq!(world.snapshot(), expected, "generation {}", generations);
+        }
+
+        // The glider never gets round the corner: it ends as a block wedged into it.
+        let block = [(6, 6), (6, 7), (7, 6), (7, 7)].map(|(x, y)| Vector { x, y });
+        assert_eq!(world.alive_cells().collect::<Vec<_>>(), block);
+        assert_eq!(world.run_until_settled(100), Settling::Settled { generation: generations, period: 1 });
+    }
+
+    // The neighbour sums seen at the top left corner and the middle of the left edge.
+    fn seam_sums(topology: Topology, live: &[(usize, usize)]) -> (u32, u32) {
+        let mut world = World::empty(&Vector { x: 5, y: 4 });
+        world.set_topology(topology);
+
+        for &(x, y) in live {
+            world.set_alive(x, y, true);
+        }
+
+        (world.evaluate(0, 0).neighbour_sum, world.evaluate(0, 1).neighbour_sum)
+    }
+
+    #[test]
+    fn neighbour_sums_at_the_seams() {
+        let everywhere: Vec<(usize, usize)> = (0..5).flat_map(|x| (0..4).map(move |y| (x, y))).collect();
+        // Cells that are only neighbours of the corner across one seam or both.
+        let right_edge = [(4, 0), (4, 1)];
+        let bottom_edge = [(0, 3), (1, 3)];
+        let far_corner = [(4, 3)];
+
+        for (topology, full, right, bottom, corner) in [
+            (Topology::Bounded, (3, 5), (0, 0), (0, 0), (0, 0)),
+            (Topology::WrapX, (5, 8), (2, 2), (0, 0), (0, 0)),
+            (Topology::WrapY, (5, 5), (0, 0), (2, 0), (0, 0)),
+            (Topology::Torus, (8, 8), (2, 2), (2, 0), (1, 0)),
+            // Everything past the edge counts as alive: 5 cells for a corner, 3 along an edge.
+            (Topology::LiveEdge, (8, 8), (5, 3), (5, 3), (5, 3)),
+        ] {
+            assert_eq!(seam_sums(topology, &everywhere), full, "{} full", topology.name());
+            assert_eq!(seam_sums(topology, &right_edge), right, "{} right edge", topology.name());
+            assert_eq!(seam_sums(topology, &bottom_edge), bottom, "{} bottom edge", topology.name());
+            assert_eq!(seam_sums(topology, &far_corner), corner, "{} far corner", topology.name());
+        }
+    }
+
+    #[test]
+    fn edge_cells_step_differently_under_each_topology() {
+        // A gapped pair on the top edge with a cell straight across the top seam from the gap.
+        let start = [(1, 0), (3, 0), (2, 4)];
+
+        for (topologies, expected) in [
+            // Nothing is near enough to anything else, so everything dies.
+            (&[Topology::Bounded, Topology::WrapX][..], &[][..]),
+            // Across the seam the gap sees three and is born, and the cell below keeps two.
+            (&[Topology::WrapY, Topology::Torus][..], &[(2, 0), (2, 4)][..]),
+            // The gap sees five and stays empty, the pair and the bottom cell survive on the three
+            // past the edge, and the middle of each side is born from them alone.
+            (&[Topology::LiveEdge][..], &[(0, 2), (0, 3), (1, 0), (2, 4), (3, 0), (4, 2), (4, 3)][..]),
+        ] {
+            for &topology in topologies {
+                let mut world = World::empty(&Vector { x: 5, y: 5 });
+                world.set_topology(topology);
+
+                for (x, y) in start {
+                    world.set_alive(x, y, true);
+                }
+
+                world.tick();
+
+                for x in 0..5 {
+                    for y in 0..5 {
+                        assert_eq!(world.cells[x][y].is_alive(), expected.contains(&(x, y)), "{} at {},{}", topology.name(), x, y);
+                    }
+                }
+            }
+        }
+    }
+
+    // Runs again as a child process with stdin closed off, which is where the engine is driven.
+    #[cfg(feature = "std")]
+    #[test]
+    fn worlds_run_without_stdin() {
+        const CHILD: &str = "RUST_CONWAY_TEST_WITHOUT_STDIN";
+
+        if std::env::var_os(CHILD).is_some() {
+            let mut world = seeded(&Vector { x: 20, y: 20 }, 1);
+            world.run_until_settled(100);
+            assert!(world.frames > 0);
+            return;
+        }
+
+        let status = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["--exact", "tests::worlds_run_without_stdin"])
+            .env(CHILD, "1")
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .status()
+            .unwrap();
+
+        assert!(status.success());
+    }
+
+    #[test]
+    fn incremental_neighbour_sums_match_a_recount() {
+        // B0 without S8 flips the background every generation, so the edge sums get used.
+        let cases = [
+            ("B3/S23", Topology::Bounded, 1),
+            ("B3/S23", Topology::Torus, 1),
+            ("B36/S23", Topology::WrapX, 1),
+            ("B3/S23", Topology::WrapY, 2),
+            ("B3/S23", Topology::LiveEdge, 1),
+            ("B0123478/S01234678", Topology::Bounded, 1),
+            ("B0/S23", Topology::WrapX, 2),
+        ];
+
+        for (seed, (rule, topology, radius)) in cases.into_iter().enumerate() {
+            let mut world = seeded(&Vector { x: 17, y: 13 }, seed as u64);
+            let mut rng = StdRng::seed_from_u64(seed as u64);
+            world.set_rule(rule.parse().unwrap());
+            world.set_topology(topology);
+            world.set_radius(radius);
+
+            for generation in 0..40 {
+                world.tick();
+
+                // Edits between ticks go through the same bookkeeping.
+                let (x, y) = (rng.gen_range(0..17), rng.gen_range(0..13));
+                world.toggle_cell(x, y);
+
+                let (neighbour_sums, edge_sums) = recounted_sums(&world);
+                assert_eq!(world.neighbour_sums, neighbour_sums, "{} {} r{} generation {}", rule, topology.name(), radius, generation);
+                assert_eq!(world.edge_sums, edge_sums, "{} {} r{} generation {}", rule, topology.name(), radius, generation);
+            }
+        }
+    }
+
+    #[test]
+    fn hexagonal_interior_cells_count_six_neighbours() {
+        let mut world = World::empty(&Vector { x: 9, y: 9 });
+        world.set_neighbourhood(Neighbourhood::Hexagonal);
+
+        for (x, y) in (0..9).flat_map(|x| (0..9).map(move |y| (x, y))) {
+            world.set_alive(x, y, true);
+        }
+
+        assert_eq!(world.evaluate(4, 4).neighbour_sum, 6);
+        assert_eq!(world.evaluate(4, 3).neighbour_sum, 6);
+
+        world.set_radius(2);
+        assert_eq!(world.evaluate(4, 4).neighbour_sum, 18);
+        assert_eq!(world.evaluate(4, 3).neighbour_sum, 18);
+    }
+
+    #[test]
+    fn hexagonal_odd_rows_lean_right() {
+        // The cells a lone live cell is counted by, which are its own neighbours.
+        let counted_by = |x: usize, y: usize| {
+            let mut world = World::empty(&Vector { x: 9, y: 9 });
+            world.set_neighbourhood(Neighbourhood::Hexagonal);
+            world.set_alive(x, y, true);
+
+            let mut counting: Vec<(usize, usize)> = (0..9)
+                .flat_map(|x| (0..9).map(move |y| (x, y)))
+                .filter(|&(x, y)| world.evaluate(x, y).neighbour_sum == 1)
+                .collect();
+            counting.sort_by_key(|&(x, y)| (y, x));
+            counting
+        };
+
+        assert_eq!(counted_by(4, 4), [(3, 3), (4, 3), (3, 4), (5, 4), (3, 5), (4, 5)]);
+        assert_eq!(counted_by(4, 3), [(4, 2), (5, 2), (3, 3), (5, 3), (4, 4), (5, 4)]);
+    }
+
+    #[test]
+    fn hexagonal_neighbour_sums_match_a_recount() {
+        // An odd height puts two even rows together at the torus seam, where counting isn't symmetric.
+        for (seed, size) in [Vector { x: 10, y: 8 }, Vector { x: 9, y: 7 }].iter().enumerate() {
+            for topology in [Topology::Bounded, Topology::Torus, Topology::LiveEdge] {
+                let mut world = seeded(size, seed as u64);
+                world.set_neighbourhood(Neighbourhood::Hexagonal);
+                world.set_rule("B2/S34".parse().unwrap());
+                world.set_topology(topology);
+
+                for generation in 0..30 {
+                    world.tick();
+
+                    let (neighbour_sums, edge_sums) = recounted_sums(&world);
+                    assert_eq!(world.neighbour_sums, neighbour_sums, "{}x{} {} generation {}", size.x, size.y, topology.name(), generation);
+                    assert_eq!(world.edge_sums, edge_sums, "{}x{} {} generation {}", size.x, size.y, topology.name(), generation);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn switching_topology_counts_across_the_seam_on_the_next_tick() {
+        // A vertical line on the right edge is three neighbours for the cell opposite it on the
+        // left edge, but only once the edges wrap.
+        let mut world = World::empty(&Vector { x: 6, y: 6 });
+
+        for y in 1..=3 {
+            world.set_alive(5, y, true);
+        }
+
+        assert_eq!(world.evaluate(0, 2).neighbour_sum, 0);
+        assert!(!world.evaluate(0, 2).next_state);
+
+        world.set_topology(Topology::Torus);
+        assert_eq!(world.evaluate(0, 2).neighbour_sum, 3);
+
+        // The line turns as a blinker straddling the seam.
+        world.tick();
+        assert_eq!(world.alive_cells().collect::<Vec<_>>(), [Vector { x: 0, y: 2 }, Vector { x: 4, y: 2 }, Vector { x: 5, y: 2 }]);
+
+        // Walled off again, the cell on the left edge is cut off from the other two at once, and
+        // the blinker dies instead of turning back upright.
+        world.set_topology(Topology::Bounded);
+        assert_eq!(world.evaluate(0, 2).neighbour_sum, 0);
+        assert_eq!(world.evaluate(5, 2).neighbour_sum, 1);
+
+        world.tick();
+        assert!(world.is_extinct());
+    }
+
+    #[test]
+    fn a_live_background_counts_past_bounded_edges() {
+        let mut world = World::empty(&Vector { x: 5, y: 5 });
+        world.set_rule("B0/S8".parse().unwrap());
+        world.tick();
+
+        // Every cell was born from the dead background, which came alive with them.
+        assert!(world.background_alive());
+        assert_eq!(world.population, 25);
+
+        // A corner now sees 3 live neighbours inside and 5 more in the background; only S8 keeps it.
+        assert_eq!(world.evaluate(0, 0).neighbour_sum, 8);
+        assert_eq!(world.evaluate(2, 2).neighbour_sum, 8);
+    }
+
+    #[test]
+    fn a_seed_always_gives_the_same_grid() {
+        let size = Vector { x: 37, y: 23 };
+        let grid = |seed| seed_grid(&size, 0.4, &mut StdRng::seed_from_u64(seed));
+
+        assert_eq!(grid(7), grid(7));
+        assert_ne!(grid(7), grid(8));
+        assert_eq!(seeded(&size, 7).snapshot(), grid(7));
+    }
+
+    #[test]
+    fn seed_seven_plays_out_as_it_always_has() {
+        let mut world = seeded(&Vector { x: 16, y: 12 }, 7);
+        let first: Vec<Vector> = world.alive_cells().take(8).collect();
+        let mut populations = vec![world.population];
+
+        for _ in 0..10 {
+            world.tick();
+            populations.push(world.population);
+        }
+
+        // Anything that changes these has moved the random stream, and --seed worlds with it.
+        assert_eq!(first, [(0, 0), (0, 1), (0, 2), (0, 4), (0, 6), (0, 7), (0, 8), (0, 11)].map(|(x, y)| Vector { x, y }));
+        assert_eq!(populations, [77, 63, 62, 61, 46, 43, 42, 31, 33, 24, 24]);
+    }
+
+    #[test]
+    fn resizing_keeps_cells_in_place_from_the_top_left() {
+        let glider = [(2, 1), (3, 2), (1, 3), (2, 3), (3, 3)].map(|(x, y)| Vector { x, y });
+        let corner = Vector { x: 9, y: 7 };
+        let mut world = World::empty(&Vector { x: 10, y: 8 });
+
+        for cell in glider.iter().chain([&corner]) {
+            world.set_alive(cell.x as usize, cell.y as usize, true);
+        }
+
+        let check = |world: &World, alive: &[Vector]| {
+            assert_eq!(world.alive_cells().collect::<Vec<Vector>>(), alive);
+            assert_eq!(world.population, alive.len() as u64);
+            assert_eq!((world.neighbour_sums.clone(), world.edge_sums.clone()), recounted_sums(world));
+
+            for (x, column) in world.cells.iter().enumerate() {
+                for (y, cell) in column.iter().enumerate() {
+                    assert_eq!(cell.coordinate, Vector { x: x as i32, y: y as i32 });
+                }
+            }
+        };
+
+        let mut alive = glider.to_vec();
+        alive.push(corner);
+        alive.sort_by_key(|cell| (cell.x, cell.y));
+
+        world.resize(&Vector { x: 14, y: 11 });
+        check(&world, &alive);
+
+        // Cropping loses the corner cell and nothing else.
+        world.resize(&Vector { x: 6, y: 5 });
+        alive.retain(|cell| *cell != corner);
+        check(&world, &alive);
+
+        // The cropped world ticks like one that started out that size.
+        let mut fresh = World::empty(&Vector { x: 6, y: 5 });
+
+        for cell in &glider {
+            fresh.set_alive(cell.x as usize, cell.y as usize, true);
+        }
+
+        world.tick();
+        fresh.tick();
+        assert_eq!(world.snapshot(), fresh.snapshot());
+    }
+
+    #[test]
+    #[should_panic(expected = "World size must be positive")]
+    fn resizing_to_nothing_panics() {
+        World::empty(&Vector { x: 4, y: 4 }).resize(&Vector { x: 0, y: 4 });
+    }
+
+    #[test]
+    fn only_worlds_that_can_stay_empty_skip_their_tick() {
+        let size = Vector { x: 5, y: 5 };
+
+        // Nothing can be born from nothing under Conway's rule, so the tick is skipped.
+        let mut world = World::empty(&size);
+        world.tick();
+        world.tick();
+        assert_eq!(world.population, 0);
+        assert!(!world.changed);
+
+        // B0 births every dead cell with no live neighbours.
+        let mut world = World::empty(&size);
+        world.set_rule("B0/S8".parse().unwrap());
+        world.tick();
+        assert_eq!(world.population, 25);
+
+        // Emptied again, the background B0 brought to life still feeds the edges under B3/S23:
+        // three past each edge, and five past the corners.
+        for x in 0..5 {
+            for y in 0..5 {
+                world.set_alive(x, y, false);
+            }
+        }
+
+        world.set_rule("B3/S23".parse().unwrap());
+        assert_eq!(world.population, 0);
+        assert!(world.background_alive());
+        world.tick();
+
+        for x in 0..5 {
+            for y in 0..5 {
+                let on_an_edge = x == 0 || x == 4 || y == 0 || y == 4;
+                let corner = (x == 0 || x == 4) && (y == 0 || y == 4);
+                assert_eq!(world.cells[x][y].is_alive(), on_an_edge && !corner, "{},{}", x, y);
+            }
+        }
+    }
+}
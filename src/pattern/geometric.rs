@@ -0,0 +1,151 @@
+// Shapes built from a formula rather than loaded from text, for symmetric starting patterns
+// that need no seed. Each comes out as a Pattern, so it can be placed or stamped like any other.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+use crate::pattern::{Metadata, Pattern};
+use crate::Vector;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Shape {
+    Diamond(i32),
+    // Radius and the gap between rings.
+    Rings(i32, i32),
+    Sierpinski(u32),
+}
+
+// Keeps the formulas well inside i32 and the patterns to a few million cells.
+const MAX_RADIUS: i32 = 1000;
+const MAX_ORDER: u32 = 10;
+
+impl Shape {
+    pub fn pattern(&self) -> Pattern {
+        let mut pattern = match *self {
+            Shape::Diamond(radius) => diamond(radius),
+            Shape::Rings(radius, spacing) => rings(radius, spacing),
+            Shape::Sierpinski(order) => sierpinski(order),
+        };
+
+        pattern.metadata.name = Some(match *self {
+            Shape::Diamond(radius) => format!("diamond:{}", radius),
+            Shape::Rings(radius, spacing) => format!("rings:{},{}", radius, spacing),
+            Shape::Sierpinski(order) => format!("sierpinski:{}", order),
+        });
+
+        pattern
+    }
+}
+
+impl FromStr for Shape {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Shape, String> {
+        let radius = |radius: &str| match radius.parse() {
+            Ok(radius) if (0..=MAX_RADIUS).contains(&radius) => Ok(radius),
+            _ => Err(format!("Invalid radius: {} (use 0 to {})", radius, MAX_RADIUS)),
+        };
+
+        match value.split_once(':') {
+            Some(("diamond", size)) => Ok(Shape::Diamond(radius(size)?)),
+            Some(("rings", size)) => {
+                let (size, spacing) = size.split_once(',').unwrap_or((size, "2"));
+
+                match spacing.parse() {
+                    Ok(spacing) if spacing > 0 => Ok(Shape::Rings(radius(size)?, spacing)),
+                    _ => Err(format!("Invalid ring spacing: {}", spacing)),
+                }
+            }
+            Some(("sierpinski", order)) => match order.parse() {
+                Ok(order) if order <= MAX_ORDER => Ok(Shape::Sierpinski(order)),
+                _ => Err(format!("Invalid Sierpinski order: {} (use 0 to {})", order, MAX_ORDER)),
+            },
+            _ => Err(format!("Unknown shape: {} (use diamond:R, rings:R or rings:R,GAP, or sierpinski:N)", value)),
+        }
+    }
+}
+
+// Every cell within `radius` steps of the centre, counting only horizontal and vertical ones:
+// 2r² + 2r + 1 cells in a square 2r + 1 across.
+pub fn diamond(radius: i32) -> Pattern {
+    centred(radius, |x, y| x.abs() + y.abs() <= radius)
+}
+
+// Circles of radius 0, `spacing`, 2 * `spacing` and so on out to `radius`, each the cells whose
+// distance from the centre rounds to that radius. Squared distances are compared against the
+// rounding bounds, doubled, so no square root is needed.
+pub fn rings(radius: i32, spacing: i32) -> Pattern {
+    centred(radius, |x, y| {
+        let distance = 4 * (x * x + y * y);
+
+        (0..=radius / spacing)
+            .map(|ring| 2 * ring * spacing)
+            .any(|diameter| distance >= (diameter - 1).max(0).pow(2) && distance < (diameter + 1).pow(2))
+    })
+}
+
+// Pascal's triangle mod 2, 2^order rows of it, as a right triangle: row y holds the odd
+// binomial coefficients C(y, x), which are the x whose bits are all set in y.
+pub fn sierpinski(order: u32) -> Pattern {
+    let rows = 1 << order;
+    let cells = (0..rows)
+        .flat_map(|y| (0..=y).filter(move |x| x & y == *x).map(move |x| Vector { x, y }))
+        .collect();
+
+    Pattern { size: Vector { x: rows, y: rows }, cells, rule: None, metadata: Metadata::default() }
+}
+
+fn centred(radius: i32, alive: impl Fn(i32, i32) -> bool) -> Pattern {
+    let mut cells = Vec::new();
+
+    for y in -radius..=radius {
+        for x in -radius..=radius {
+            if alive(x, y) {
+                cells.push(Vector { x: x + radius, y: y + radius });
+            }
+        }
+    }
+
+    Pattern { size: Vector { x: 2 * radius + 1, y: 2 * radius + 1 }, cells, rule: None, metadata: Metadata::default() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Neighbourhood, World};
+
+    #[test]
+    fn diamonds_have_two_r_squared_plus_two_r_plus_one_cells() {
+        for radius in 0..=30 {
+            let pattern = diamond(radius);
+
+            assert_eq!(pattern.cells.len() as i32, 2 * radius * radius + 2 * radius + 1, "radius {}", radius);
+            assert_eq!(pattern.size, Vector { x: 2 * radius + 1, y: 2 * radius + 1 });
+        }
+    }
+
+    #[test]
+    fn a_diamond_fills_a_von_neumann_neighbourhood_of_its_radius() {
+        for radius in 1..=5 {
+            let side = 2 * radius + 3;
+            let mut world = World::from_pattern(&diamond(radius), &Vector { x: side, y: side });
+            world.set_neighbourhood(Neighbourhood::VonNeumann);
+            world.set_radius(radius);
+
+            // Every cell in the diamond but the centre itself, and no more once the rest of the
+            // world is alive too.
+            let centre = (radius + 1) as usize;
+            assert_eq!(world.evaluate(centre, centre).neighbour_sum as i32, 2 * radius * radius + 2 * radius, "radius {}", radius);
+
+            for x in 0..side as usize {
+                for y in 0..side as usize {
+                    world.set_alive(x, y, true);
+                }
+            }
+
+            assert_eq!(world.evaluate(centre, centre).neighbour_sum as i32, 2 * radius * radius + 2 * radius, "radius {}", radius);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+// Scenes composed from several patterns, listed one to a line:
+//
+//     # Comments and blank lines are skipped.
+//     glider at 10,10 rot90
+//     ships/lwss.rle at 0,20 flipx
+//
+// Each line names a catalog pattern or a file, where its top left corner goes, and optionally
+// one of rot90, rot180, rot270, flipx or flipy. The composed pattern spans from (0, 0) to the far
+// corner of whatever is placed furthest out.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use crate::error::ConwayError;
+use crate::pattern::{Metadata, Orientation, Pattern};
+use crate::Vector;
+
+// Files are left to `load`, which gets the name as written and turns any failure into a message
+// for the line that named it. Catalog names are looked up first.
+pub fn parse(input: &str, mut load: impl FnMut(&str) -> Result<Pattern, String>) -> Result<Pattern, ConwayError> {
+    let mut size = Vector { x: 0, y: 0 };
+    let mut cells = Vec::new();
+    let mut placed = 0;
+
+    let lines = input.lines()
+        .map(str::trim)
+        .enumerate()
+        .map(|(i, line)| (i + 1, line))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+    for (line, text) in lines {
+        let invalid = |context: String| ConwayError::ParseError { line, context };
+        let words: Vec<&str> = text.split_whitespace().collect();
+
+        let (name, offset, orientation) = match words.as_slice() {
+            [name, "at", offset] => (*name, *offset, Orientation::Identity),
+            [name, "at", offset, orientation] => (*name, *offset, orientation.parse().map_err(invalid)?),
+            _ => return Err(invalid(format!("Expected NAME at X,Y with an optional transform: {}", text))),
+        };
+
+        let at = parse_offset(offset).ok_or_else(|| invalid(format!("Invalid offset: {} (use X,Y, neither negative)", offset)))?;
+        let pattern = match Pattern::named(name) {
+            Some(pattern) => pattern,
+            None => load(name).map_err(invalid)?,
+        }
+        .oriented(orientation);
+
+        let far_corner = (at.x.checked_add(pattern.size.x), at.y.checked_add(pattern.size.y));
+        let (Some(right), Some(bottom)) = far_corner else {
+            return Err(invalid(format!("{} at {} reaches too far", name, offset)));
+        };
+
+        size = Vector { x: size.x.max(right), y: size.y.max(bottom) };
+        cells.extend(pattern.cells.iter().map(|cell| Vector { x: at.x + cell.x, y: at.y + cell.y }));
+        placed += 1;
+    }
+
+    if placed == 0 {
+        return Err(ConwayError::ParseError { line: input.lines().count() + 1, context: "The manifest lists no patterns".to_string() });
+    }
+
+    Ok(Pattern { size, cells, rule: None, metadata: Metadata::default() })
+}
+
+fn parse_offset(offset: &str) -> Option<Vector> {
+    let (x, y) = offset.split_once(',')?;
+    let offset = Vector { x: x.trim().parse().ok()?, y: y.trim().parse().ok()? };
+
+    (offset.x >= 0 && offset.y >= 0).then_some(offset)
+}
@@ -0,0 +1,102 @@
+// Brute-force search for a state that could have produced part of the current world.
+
+use std::collections::HashMap;
+
+use ratatui::layout::Rect;
+
+use crate::{CellState, Vector, World};
+
+pub const MAX_PREDECESSOR_CELLS: usize = 25;
+
+struct Target {
+    bit: u32,
+    // Neighbour bits grouped by weight, so a sum is a few popcounts.
+    neighbours: Vec<(u32, u32)>,
+    alive: bool,
+}
+
+impl<S: CellState> World<S> {
+    // The cells whose previous states decide the region: the region itself plus a margin of
+    // the neighbourhood radius, clipped to the world.
+    pub fn predecessor_patch(&self, region: &Rect) -> Vec<Vector> {
+        let mut patch = Vec::new();
+        let max = Vector { x: region.right() as i32 + self.radius, y: region.bottom() as i32 + self.radius };
+
+        for x in region.x as i32 - self.radius..max.x {
+            for y in region.y as i32 - self.radius..max.y {
+                let coordinate = self.topology.wrap(&Vector { x, y }, &Vector { x: 0, y: 0 }, &self.size);
+
+                if coordinate.out_of_bounds(&Vector { x: 0, y: 0 }, &self.size) {
+                    continue;
+                }
+
+                if !patch.contains(&coordinate) {
+                    patch.push(coordinate);
+                }
+            }
+        }
+
+        patch
+    }
+
+    // Tries every state of the predecessor patch, so callers must keep it within
+    // MAX_PREDECESSOR_CELLS; larger regions are reported as having no predecessor.
+    pub fn has_local_predecessor(&self, region: &Rect) -> bool {
+        let patch = self.predecessor_patch(region);
+
+        if patch.len() > MAX_PREDECESSOR_CELLS {
+            return false;
+        }
+
+        let bits: HashMap<(i32, i32), u32> = patch.iter()
+            .enumerate()
+            .map(|(bit, coordinate)| ((coordinate.x, coordinate.y), 1 << bit))
+            .collect();
+
+        let mut targets = Vec::new();
+
+        for x in region.left()..region.right() {
+            for y in region.top()..region.bottom() {
+                let Some(&bit) = bits.get(&(x as i32, y as i32)) else {
+                    continue;
+                };
+
+                let mut neighbours: Vec<(u32, u32)> = Vec::new();
+
+                for offset in self.offsets_from(self.min.y + y as i32) {
+                    let neighbour = self.topology.wrap(
+                        &Vector { x: x as i32 + offset.x, y: y as i32 + offset.y },
+                        &Vector { x: 0, y: 0 },
+                        &self.size,
+                    );
+
+                    let Some(&neighbour_bit) = bits.get(&(neighbour.x, neighbour.y)) else {
+                        continue;
+                    };
+
+                    match neighbours.iter_mut().find(|(_, weight)| *weight == offset.weight) {
+                        Some((mask, _)) => *mask |= neighbour_bit,
+                        None => neighbours.push((neighbour_bit, offset.weight)),
+                    }
+                }
+
+                targets.push(Target {
+                    bit,
+                    neighbours,
+                    alive: self.cells[x as usize][y as usize].is_alive(),
+                });
+            }
+        }
+
+        (0..1u32 << patch.len()).any(|states| {
+            targets.iter().all(|target| {
+                let state = if states & target.bit != 0 { S::live() } else { S::dead() };
+                let sum = target.neighbours.iter()
+                    .map(|(mask, weight)| (states & mask).count_ones() * weight)
+                    .sum();
+
+                state.next(sum, &self.rule).is_live_for_counting() == target.alive
+            })
+        })
+    }
+}
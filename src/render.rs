@@ -0,0 +1,29 @@
+// Drawing a world for some frontend, kept apart from the engine so another one (a web canvas,
+// say) can be added without touching the terminal or PNG code.
+
+use crate::World;
+#[cfg(feature = "std")]
+use crate::CellState;
+#[cfg(feature = "std")]
+use ratatui::text::Text;
+
+pub trait Renderer<S = bool> {
+    type Output;
+
+    fn render(&self, world: &World<S>) -> Self::Output;
+}
+
+// The plain and textured terminal views, as text for a ratatui widget.
+#[cfg(feature = "std")]
+pub struct TerminalRenderer {
+    pub textured: bool,
+}
+
+#[cfg(feature = "std")]
+impl<S: CellState> Renderer<S> for TerminalRenderer {
+    type Output = Text<'static>;
+
+    fn render(&self, world: &World<S>) -> Text<'static> {
+        world.draw_world(self.textured)
+    }
+}
@@ -0,0 +1,282 @@
+// Run Length Encoded patterns, as used by most Life software.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use crate::error::ConwayError;
+use crate::pattern::{Metadata, Pattern};
+use crate::{Vector, World};
+
+const MAX_LINE_LENGTH: usize = 70;
+// The longest side a pattern or a world loaded from one may have, whether the header gives it or
+// the cells are measured, so a few characters of input can't ask for a grid that fills memory.
+pub const MAX_SIDE: i32 = 10_000;
+
+impl World {
+    // Sized from the header plus `padding` on each side, and run under the header's rule. A
+    // header that leaves out x or y falls back to `size` when one is given, and otherwise to the
+    // cells' own extent. A size with no cells in it at all is out of bounds, and so is one with
+    // a side longer than MAX_SIDE once padded.
+    pub fn from_rle(text: &str, padding: i32, size: Option<&Vector>) -> Result<World, ConwayError> {
+        let (pattern, sized) = parse_sized(text)?;
+
+        let size = match (sized, size) {
+            (false, Some(size)) => *size,
+            _ => Vector {
+                x: pattern.size.x.saturating_add(padding.saturating_mul(2)),
+                y: pattern.size.y.saturating_add(padding.saturating_mul(2)),
+            },
+        };
+
+        if size.x <= 0 || size.y <= 0 {
+            return Err(ConwayError::OutOfBounds { cell: Vector { x: 0, y: 0 }, size });
+        }
+
+        check_size(&size)?;
+        Ok(World::from_pattern(&pattern, &size))
+    }
+
+    pub fn to_rle(&self) -> String {
+        let mut tokens = Vec::new();
+        let mut pending_rows = 0;
+
+        for y in 0..self.size.y {
+            let mut runs: Vec<(u32, char)> = Vec::new();
+
+            for x in 0..self.size.x {
+                let tag = if self.cells[x as usize][y as usize].is_alive() { 'o' } else { 'b' };
+
+                match runs.last_mut() {
+                    Some((count, last_tag)) if *last_tag == tag => *count += 1,
+                    _ => runs.push((1, tag)),
+                }
+            }
+
+            if let Some((_, 'b')) = runs.last() {
+                runs.pop();
+            }
+
+            if runs.is_empty() {
+                pending_rows += 1;
+                continue;
+            }
+
+            match tokens.is_empty() {
+                true if pending_rows > 0 => tokens.push(run_token(pending_rows, '$')),
+                true => {}
+                false => tokens.push(run_token(pending_rows + 1, '$')),
+            }
+
+            pending_rows = 0;
+            tokens.extend(runs.into_iter().map(|(count, tag)| run_token(count, tag)));
+        }
+
+        tokens.push("!".to_string());
+
+        let mut rle = String::new();
+
+        if let Some(name) = &self.metadata.name {
+            rle.push_str(&format!("#N {}\n", name));
+        }
+
+        for comment in &self.metadata.comments {
+            rle.push_str(&format!("#C {}\n", comment));
+        }
+
+        rle.push_str(&format!("x = {}, y = {}, rule = {}\n", self.size.x, self.size.y, self.rule));
+        let mut line = String::new();
+
+        for token in tokens {
+            if line.len() + token.len() > MAX_LINE_LENGTH {
+                rle.push_str(&line);
+                rle.push('\n');
+                line.clear();
+            }
+
+            line.push_str(&token);
+        }
+
+        rle.push_str(&line);
+        rle.push('\n');
+        rle
+    }
+}
+
+pub fn parse(input: &str) -> Result<Pattern, ConwayError> {
+    parse_sized(input).map(|(pattern, _)| pattern)
+}
+
+// Also says whether the header gave both sides, rather than them being measured from the cells.
+fn parse_sized(input: &str) -> Result<(Pattern, bool), ConwayError> {
+    let invalid = |line, context| ConwayError::ParseError { line, context };
+    let mut metadata = Metadata::default();
+
+    // Other `#` lines, like #O for the author or #R for the position, are skipped.
+    for line in input.lines().map(str::trim).filter(|line| line.starts_with('#')) {
+        match line.get(..2) {
+            Some("#N") => metadata.name = Some(line[2..].trim().to_string()).filter(|name| !name.is_empty()),
+            Some("#C" | "#c") => metadata.comments.push(line[2..].trim().to_string()),
+            _ => {}
+        }
+    }
+
+    let mut lines = input.lines()
+        .map(str::trim)
+        .enumerate()
+        .map(|(i, line)| (i + 1, line))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+    let (header_line, header) = lines.next()
+        .ok_or_else(|| invalid(input.lines().count() + 1, "Missing RLE header".to_string()))?;
+    let (mut width, mut height) = (None, None);
+    let mut rule = None;
+
+    let length = |value: &str| value.parse::<i32>().ok().filter(|length| *length >= 0);
+
+    for field in header.split(',') {
+        let (key, value) = field.split_once('=').ok_or_else(|| invalid(header_line, format!("Invalid RLE header: {}", header)))?;
+        let value = value.trim();
+
+        match key.trim() {
+            "x" => width = Some(length(value).ok_or_else(|| invalid(header_line, format!("Invalid RLE width: {}", value)))?),
+            "y" => height = Some(length(value).ok_or_else(|| invalid(header_line, format!("Invalid RLE height: {}", value)))?),
+            "rule" => rule = Some(value.parse().map_err(|message| invalid(header_line, message))?),
+            _ => {}
+        }
+    }
+
+    let mut cells = Vec::new();
+    let mut position = Vector { x: 0, y: 0 };
+    let mut count = String::new();
+
+    'lines: for (line, tags) in lines {
+        for tag in tags.chars() {
+            if tag.is_ascii_digit() {
+                count.push(tag);
+                continue;
+            }
+
+            let run: i32 = match count.is_empty() {
+                true => 1,
+                false => count.parse().map_err(|_| invalid(line, format!("Invalid RLE run: {}", count)))?,
+            };
+            count.clear();
+
+            let too_long = || invalid(line, format!("RLE run too long: {}", run));
+
+            match tag {
+                '!' => break 'lines,
+                '$' => {
+                    position.x = 0;
+                    position.y = position.y.checked_add(run).ok_or_else(too_long)?;
+                }
+                'b' | '.' => position.x = position.x.checked_add(run).ok_or_else(too_long)?,
+                tag if tag.is_ascii_alphabetic() => {
+                    let limit = width.unwrap_or(MAX_SIDE);
+
+                    if position.x.checked_add(run).is_none_or(|end| end > limit) {
+                        return Err(invalid(line, format!("RLE run of {} cells runs past the {} cell width", run, limit)));
+                    }
+
+                    // Caught here rather than once the cells are all read, so rows and rows of
+                    // them can't pile up first.
+                    check_size(&Vector { x: 1, y: position.y + 1 })?;
+
+                    for _ in 0..run {
+                        cells.push(position);
+                        position.x += 1;
+                    }
+                }
+                tag if tag.is_whitespace() => {}
+                tag => return Err(invalid(line, format!("Invalid RLE tag: {}", tag))),
+            }
+        }
+    }
+
+    let size = Vector {
+        x: width.unwrap_or_else(|| cells.iter().map(|cell| cell.x + 1).max().unwrap_or(0)),
+        y: height.unwrap_or_else(|| cells.iter().map(|cell| cell.y + 1).max().unwrap_or(0)),
+    };
+
+    check_size(&size)?;
+
+    if let Some(cell) = cells.iter().find(|cell| cell.out_of_bounds(&Vector { x: 0, y: 0 }, &size)) {
+        return Err(ConwayError::OutOfBounds { cell: *cell, size });
+    }
+
+    Ok((Pattern { size, cells, rule, metadata }, width.is_some() && height.is_some()))
+}
+
+// Reports the far corner of a size that is too big as outside the largest one allowed.
+fn check_size(size: &Vector) -> Result<(), ConwayError> {
+    let largest = Vector { x: MAX_SIDE, y: MAX_SIDE };
+
+    match size.x > largest.x || size.y > largest.y {
+        true => Err(ConwayError::OutOfBounds { cell: Vector { x: size.x - 1, y: size.y - 1 }, size: largest }),
+        false => Ok(()),
+    }
+}
+
+fn run_token(count: u32, tag: char) -> String {
+    match count {
+        1 => tag.to_string(),
+        _ => format!("{}{}", count, tag),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule::Rule;
+
+    // The HighLife replicator, which copies itself under B36/S23 and falls apart under Conway's rule.
+    const REPLICATOR: &str = "#N Replicator\nx = 5, y = 5, rule = B36/S23\n2b3o$bo2bo$o3bo$o2bo$3o!\n";
+
+    #[test]
+    fn highlife_survives_a_round_trip() {
+        let world = World::from_rle(REPLICATOR, 0, None).unwrap();
+        let highlife: Rule = "B36/S23".parse().unwrap();
+        assert_eq!(world.rule, highlife);
+
+        let saved = world.to_rle();
+        assert!(saved.contains("x = 5, y = 5, rule = B36/S23\n"), "{}", saved);
+
+        let loaded = World::from_rle(&saved, 0, None).unwrap();
+        assert_eq!(loaded.rule, highlife);
+        assert_eq!(loaded.snapshot(), world.snapshot());
+        assert_eq!(loaded.metadata.name.as_deref(), Some("Replicator"));
+        assert_eq!(loaded.to_rle(), saved);
+    }
+
+    #[test]
+    fn refuses_sizes_too_big_to_allocate() {
+        let too_big = |result: Result<World, ConwayError>| match result {
+            Err(ConwayError::OutOfBounds { size, .. }) => size == Vector { x: MAX_SIDE, y: MAX_SIDE },
+            _ => false,
+        };
+
+        assert!(too_big(World::from_rle("x = 100000, y = 100000\n!", 0, None)));
+        assert!(too_big(World::from_rle("x = 3, y = 20000\n!", 0, None)));
+        // Measured from the cells rather than given in the header.
+        assert!(too_big(World::from_rle("rule = B3/S23\n20000$o!", 0, None)));
+        assert!(too_big(World::from_rle("x = 1, y = 1\no!", MAX_SIDE, None)));
+
+        let largest = World::from_rle(&format!("x = {}, y = 1\n!", MAX_SIDE), 0, None).unwrap();
+        assert_eq!(largest.size, Vector { x: MAX_SIDE, y: 1 });
+    }
+
+    #[test]
+    fn a_round_tripped_world_evolves_the_same() {
+        let mut world = World::from_rle(REPLICATOR, 10, None).unwrap();
+        let mut loaded = World::from_rle(&world.to_rle(), 0, None).unwrap();
+
+        for _ in 0..12 {
+            world.tick();
+            loaded.tick();
+        }
+
+        assert_eq!(loaded.snapshot(), world.snapshot());
+        assert_eq!(loaded.population, world.population);
+    }
+}
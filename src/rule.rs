@@ -0,0 +1,407 @@
+// Totalistic rules, optionally over a weighted neighbourhood.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::RangeInclusive;
+use core::str::FromStr;
+use rand::Rng;
+
+use crate::Neighbourhood;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Rule {
+    pub birth: Vec<RangeInclusive<u32>>,
+    pub survival: Vec<RangeInclusive<u32>>,
+}
+
+impl Rule {
+    pub fn conway() -> Rule {
+        Rule {
+            birth: vec![3..=3],
+            survival: vec![2..=3],
+        }
+    }
+
+    pub fn named(name: &str) -> Option<Rule> {
+        let (_, rule) = RULE_PRESETS.iter().find(|(preset_name, _)| preset_name.eq_ignore_ascii_case(name))?;
+
+        rule.parse().ok()
+    }
+
+    // A coin flip for each count from 0 to 8, for birth and for survival. An interesting rule is
+    // drawn again until nothing is born from empty space (B0), something can be born at all, and
+    // not every count gives birth, since those fill or empty the world straight away.
+    pub fn random(interesting: bool, rng: &mut impl Rng) -> Rule {
+        loop {
+            let mut counts = || (0..=8).filter(|_| rng.gen_bool(0.5)).map(|count| count..=count).collect();
+            let rule = Rule { birth: counts(), survival: counts() };
+            let births = rule.birth.len();
+
+            if !interesting || (!rule.next_state(false, 0) && births > 0 && births < 8) {
+                return rule;
+            }
+        }
+    }
+
+    // Adds `total` to the birth or survival counts, or takes it out, splitting whichever range
+    // held it. Nothing stops the rule ending up with no counts at all, or with every one.
+    pub fn toggle(&mut self, alive: bool, total: u32) {
+        let thresholds = if alive { &mut self.survival } else { &mut self.birth };
+
+        match thresholds.iter().position(|range| range.contains(&total)) {
+            Some(index) => {
+                let range = thresholds.remove(index);
+
+                if *range.start() < total {
+                    thresholds.push(*range.start()..=total - 1);
+                }
+
+                if total < *range.end() {
+                    thresholds.push(total + 1..=*range.end());
+                }
+            }
+            None => thresholds.push(total..=total),
+        }
+
+        thresholds.sort_by_key(|range| *range.start());
+    }
+
+    pub fn next_state(&self, alive: bool, total: u32) -> bool {
+        let thresholds = if alive { &self.survival } else { &self.birth };
+
+        thresholds.iter().any(|range| range.contains(&total))
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "B{}/S{}", format_counts(&self.birth), format_counts(&self.survival))
+    }
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    // Takes a preset name, B3/S23 notation, or the older survival-first 23/3 still found in RLE
+    // headers.
+    fn from_str(value: &str) -> Result<Rule, String> {
+        if !value.contains('/') {
+            return Rule::named(value.trim()).ok_or_else(|| format!("Invalid rule: {}", value));
+        }
+
+        let invalid = || format!("Invalid rule: {}", value);
+        let (first, second) = value.trim().split_once('/').ok_or_else(invalid)?;
+
+        let (birth, survival) = match (first.strip_prefix(['B', 'b']), second.strip_prefix(['S', 's'])) {
+            (Some(birth), Some(survival)) => (birth, survival),
+            (None, None) if !first.starts_with(['S', 's']) && !second.starts_with(['B', 'b']) => (second, first),
+            _ => return Err(invalid()),
+        };
+
+        Ok(Rule {
+            birth: parse_counts(birth).ok_or_else(invalid)?,
+            survival: parse_counts(survival).ok_or_else(invalid)?,
+        })
+    }
+}
+
+// A Larger than Life rule in Golly's R2,C0,M1,S2..3,B3..3,NM notation: a neighbourhood radius,
+// whether the middle cell counts itself (M1), and one inclusive range each for survival and
+// birth. NM picks the Moore neighbourhood (the default) and NN von Neumann.
+pub struct LargerThanLife {
+    pub radius: i32,
+    pub include_centre: bool,
+    pub neighbourhood: Neighbourhood,
+    pub rule: Rule,
+}
+
+impl fmt::Display for LargerThanLife {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let range = |ranges: &[RangeInclusive<u32>]| match ranges.first() {
+            Some(range) => format!("{}..{}", range.start(), range.end()),
+            None => "0..0".to_string(),
+        };
+
+        write!(
+            f,
+            "R{},C0,M{},S{},B{},N{}",
+            self.radius,
+            self.include_centre as u8,
+            range(&self.rule.survival),
+            range(&self.rule.birth),
+            if self.neighbourhood == Neighbourhood::VonNeumann { "N" } else { "M" }
+        )
+    }
+}
+
+impl FromStr for LargerThanLife {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<LargerThanLife, String> {
+        let invalid = || format!("Invalid Larger than Life rule: {}", value);
+        let mut radius = None;
+        let mut include_centre = false;
+        let mut neighbourhood = Neighbourhood::Moore;
+        let mut survival = None;
+        let mut birth = None;
+
+        for field in value.trim().split(',').map(str::trim) {
+            let (key, value) = field.split_at(field.chars().next().ok_or_else(invalid)?.len_utf8());
+
+            match key.to_ascii_uppercase().as_str() {
+                "R" => radius = Some(value.parse::<i32>().ok().filter(|radius| *radius > 0).ok_or_else(invalid)?),
+                "C" if value == "0" || value == "2" => {}
+                "C" => return Err(format!("Only two-state Larger than Life rules are supported: {}", field)),
+                "M" => include_centre = match value {
+                    "0" => false,
+                    "1" => true,
+                    _ => return Err(invalid()),
+                },
+                "S" => survival = Some(parse_range(value).ok_or_else(invalid)?),
+                "B" => birth = Some(parse_range(value).ok_or_else(invalid)?),
+                "N" => neighbourhood = match value {
+                    "M" | "m" => Neighbourhood::Moore,
+                    "N" | "n" => Neighbourhood::VonNeumann,
+                    _ => return Err(format!("Unsupported Larger than Life neighbourhood: {}", field)),
+                },
+                _ => return Err(invalid()),
+            }
+        }
+
+        Ok(LargerThanLife {
+            radius: radius.ok_or_else(invalid)?,
+            include_centre,
+            neighbourhood,
+            rule: Rule {
+                birth: vec![birth.ok_or_else(invalid)?],
+                survival: vec![survival.ok_or_else(invalid)?],
+            },
+        })
+    }
+}
+
+// Either a single count or min..max.
+fn parse_range(range: &str) -> Option<RangeInclusive<u32>> {
+    match range.split_once("..") {
+        Some((min, max)) => Some(min.parse().ok()?..=max.parse().ok()?),
+        None => range.parse().ok().map(|count| count..=count),
+    }
+}
+
+fn parse_counts(counts: &str) -> Option<Vec<RangeInclusive<u32>>> {
+    let counts: Option<Vec<u32>> = match counts.contains(',') {
+        true => counts.split(',').map(|count| count.trim().parse().ok()).collect(),
+        false => counts.chars().map(|count| count.to_digit(10)).collect(),
+    };
+
+    Some(counts?.into_iter().map(|count| count..=count).collect())
+}
+
+fn format_counts(ranges: &[RangeInclusive<u32>]) -> String {
+    let counts: Vec<u32> = ranges.iter().flat_map(|range| range.clone()).collect();
+    let separator = if counts.iter().all(|count| *count <= 9) { "" } else { "," };
+
+    counts.iter()
+        .map(|count| count.to_string())
+        .collect::<Vec<String>>()
+        .join(separator)
+}
+
+// Well-known rules by name. Day & Night and Anneal are both symmetric under swapping live and
+// dead cells, so a pattern's negative evolves into the negative of its future.
+pub const RULE_PRESETS: &[(&str, &str)] = &[
+    ("life", "B3/S23"),
+    ("highlife", "B36/S23"),
+    ("day-and-night", "B3678/S34678"),
+    ("anneal", "B4678/S35678"),
+    ("replicator", "B1357/S1357"),
+];
+
+pub struct Weights {
+    pub name: &'static str,
+    pub radius: i32,
+    values: Vec<Vec<u32>>,
+}
+
+impl Weights {
+    pub fn uniform(radius: i32) -> Weights {
+        let side = (radius * 2 + 1) as usize;
+
+        Weights {
+            name: "uniform",
+            radius,
+            values: vec![vec![1; side]; side],
+        }
+    }
+
+    pub fn get(&self, dx: i32, dy: i32) -> u32 {
+        self.values[(dx + self.radius) as usize][(dy + self.radius) as usize]
+    }
+}
+
+pub struct WeightedPreset {
+    pub name: &'static str,
+    pub weights: [[u32; 3]; 3],
+    pub birth: &'static [RangeInclusive<u32>],
+    pub survival: &'static [RangeInclusive<u32>],
+}
+
+impl WeightedPreset {
+    pub fn weights(&self) -> Weights {
+        Weights {
+            name: self.name,
+            radius: 1,
+            values: self.weights.iter().map(|column| column.to_vec()).collect(),
+        }
+    }
+
+    pub fn rule(&self) -> Rule {
+        Rule {
+            birth: self.birth.to_vec(),
+            survival: self.survival.to_vec(),
+        }
+    }
+}
+
+pub const WEIGHTED_PRESETS: &[WeightedPreset] = &[
+    WeightedPreset {
+        name: "uniform",
+        weights: [[1, 1, 1], [1, 0, 1], [1, 1, 1]],
+        birth: &[3..=3],
+        survival: &[2..=3],
+    },
+    WeightedPreset {
+        name: "orthogonal",
+        weights: [[1, 2, 1], [2, 0, 2], [1, 2, 1]],
+        birth: &[4..=5],
+        survival: &[4..=6],
+    },
+    WeightedPreset {
+        name: "corner",
+        weights: [[2, 1, 2], [1, 0, 1], [2, 1, 2]],
+        birth: &[3..=3],
+        survival: &[3..=4],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Topology, Vector, World};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn parses_the_symmetric_presets() {
+        for (name, notation) in [("day-and-night", "B3678/S34678"), ("anneal", "B4678/S35678")] {
+            let rule: Rule = name.parse().unwrap();
+
+            assert_eq!(rule, notation.parse().unwrap());
+            assert_eq!(rule.to_string(), notation);
+            assert_eq!(name.to_uppercase().parse::<Rule>().unwrap(), rule);
+        }
+    }
+
+    #[test]
+    fn day_and_night_keeps_a_block_and_loses_a_blinker() {
+        let mut world = World::empty(&Vector { x: 12, y: 7 });
+        world.set_rule("day-and-night".parse().unwrap());
+
+        for (x, y) in [(1, 1), (2, 1), (1, 2), (2, 2), (8, 2), (8, 3), (8, 4)] {
+            world.set_alive(x, y, true);
+        }
+
+        // The block's cells have three neighbours each, which S3 keeps. The blinker's middle only
+        // has two, so it dies, and the cells either side of it are born with three.
+        world.tick();
+        let block = [Vector { x: 1, y: 1 }, Vector { x: 1, y: 2 }, Vector { x: 2, y: 1 }, Vector { x: 2, y: 2 }];
+        let mut expected = block.to_vec();
+        expected.extend([Vector { x: 7, y: 3 }, Vector { x: 9, y: 3 }]);
+        assert_eq!(world.alive_cells().collect::<Vec<Vector>>(), expected);
+
+        // Two cells apart with nothing between them to give birth to.
+        world.tick();
+        assert_eq!(world.alive_cells().collect::<Vec<Vector>>(), block);
+    }
+
+    #[test]
+    fn negatives_evolve_into_negatives() {
+        for preset in ["day-and-night", "anneal"] {
+            let size = Vector { x: 12, y: 10 };
+            let mut world = World::new(&size, 0.4, &mut StdRng::seed_from_u64(3));
+            let mut negative = World::empty(&size);
+
+            // On a torus, so the dead space past bounded edges doesn't break the symmetry.
+            for world in [&mut world, &mut negative] {
+                world.set_rule(preset.parse().unwrap());
+                world.set_topology(Topology::Torus);
+            }
+
+            for x in 0..size.x as usize {
+                for y in 0..size.y as usize {
+                    negative.set_alive(x, y, !world.cells[x][y].is_alive());
+                }
+            }
+
+            for generation in 1..=10 {
+                world.tick();
+                negative.tick();
+
+                for x in 0..size.x as usize {
+                    for y in 0..size.y as usize {
+                        assert_ne!(world.cells[x][y].is_alive(), negative.cells[x][y].is_alive(), "{} at {},{} in generation {}", preset, x, y, generation);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn parses_a_larger_than_life_rule() {
+        let rule: LargerThanLife = "R2,C0,M1,S2..3,B3..3".parse().unwrap();
+
+        assert_eq!(rule.radius, 2);
+        assert!(rule.include_centre);
+        assert!(rule.neighbourhood == Neighbourhood::Moore);
+        assert_eq!(rule.rule, Rule { birth: vec![3..=3], survival: vec![2..=3] });
+        assert_eq!(rule.to_string(), "R2,C0,M1,S2..3,B3..3,NM");
+
+        let von_neumann: LargerThanLife = "R3,C2,M0,S4,B5..9,NN".parse().unwrap();
+        assert!(von_neumann.neighbourhood == Neighbourhood::VonNeumann);
+        assert_eq!(von_neumann.rule, Rule { birth: vec![5..=9], survival: vec![4..=4] });
+        assert_eq!(von_neumann.to_string().parse::<LargerThanLife>().unwrap().rule, von_neumann.rule);
+    }
+
+    #[test]
+    fn rejects_malformed_larger_than_life_rules() {
+        for rule in ["", "R0,C0,M0,S2..3,B3..3", "R2,C3,M0,S2..3,B3..3", "R2,C0,M2,S2..3,B3..3", "R2,C0,M0,B3..3", "R2,C0,M0,S2..x,B3", "R2,NX,S1,B1"] {
+            assert!(rule.parse::<LargerThanLife>().is_err(), "{}", rule);
+        }
+    }
+
+    #[test]
+    fn larger_than_life_births_reach_two_cells_away() {
+        let mut world = World::empty(&Vector { x: 11, y: 11 });
+        world.set_larger_than_life(&"R2,C0,M1,S2..3,B3..3".parse().unwrap());
+
+        for x in 4..=6 {
+            world.set_alive(x, 4, true);
+        }
+
+        // The middle of the line counts itself along with its two neighbours, and the cell two
+        // rows below it sees all three, which radius 1 would miss.
+        assert_eq!(world.evaluate(5, 4).neighbour_sum, 3);
+        assert_eq!(world.evaluate(5, 6).neighbour_sum, 3);
+        assert_eq!(world.evaluate(5, 7).neighbour_sum, 0);
+
+        world.tick();
+
+        assert!(world.cells[5][4].is_alive());
+        assert!(world.cells[5][6].is_alive());
+        assert!(!world.cells[5][7].is_alive());
+    }
+}
@@ -0,0 +1,83 @@
+// A one-line JSON summary of a finished headless run, for scripts. Fields come out in the order
+// they're declared, with null for anything a run didn't find.
+
+use core::fmt;
+
+use serde::Serialize;
+
+use crate::World;
+
+#[derive(Serialize)]
+pub struct RunSummary {
+    pub frames: u64,
+    pub population: u64,
+    pub stabilized: bool,
+    pub extinct: bool,
+    pub period: Option<u64>,
+    pub seed: Option<u64>,
+    // The generation the world fell into its cycle, when a run measured that.
+    pub settled_at: Option<u64>,
+}
+
+impl RunSummary {
+    pub fn from_world(world: &World) -> RunSummary {
+        RunSummary {
+            frames: world.frames,
+            population: world.population,
+            stabilized: !world.changed,
+            extinct: world.is_extinct(),
+            period: world.period,
+            seed: world.seed,
+            settled_at: None,
+        }
+    }
+}
+
+impl fmt::Display for RunSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&serde_json::to_string(self).map_err(|_| fmt::Error)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vector;
+    use alloc::string::ToString;
+
+    #[test]
+    fn a_fresh_world_leaves_what_it_has_not_found_null() {
+        let mut world = World::empty(&Vector { x: 4, y: 4 });
+        world.set_alive(1, 1, true);
+
+        assert_eq!(
+            RunSummary::from_world(&world).to_string(),
+            r#"{"frames":0,"population":1,"stabilized":true,"extinct":false,"period":null,"seed":null,"settled_at":null}"#
+        );
+    }
+
+    #[test]
+    fn a_settled_blinker_reports_its_period() {
+        let mut world = World::empty(&Vector { x: 5, y: 5 });
+        world.seed = Some(42);
+
+        for x in 1..=3 {
+            world.set_alive(x, 2, true);
+        }
+
+        for _ in 0..4 {
+            world.tick();
+        }
+
+        let mut summary = RunSummary::from_world(&world);
+        summary.settled_at = Some(0);
+        let parsed: serde_json::Value = serde_json::from_str(&summary.to_string()).unwrap();
+
+        assert_eq!(parsed["frames"], 4);
+        assert_eq!(parsed["population"], 3);
+        assert_eq!(parsed["extinct"], false);
+        assert_eq!(parsed["period"], 2);
+        assert_eq!(parsed["seed"], 42);
+        assert_eq!(parsed["settled_at"], 0);
+    }
+}
@@ -0,0 +1,42 @@
+// Fading trails behind moving patterns: the generation each cell was last seen alive.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{CellState, World};
+
+#[derive(Default)]
+pub struct Trails {
+    // Indexed [x][y] like `World::cells`. None for cells never seen alive.
+    last_alive: Vec<Vec<Option<u64>>>,
+}
+
+impl Trails {
+    // Only sees the generations it is shown, so a cell that lives and dies again between two
+    // updates leaves no trail.
+    pub fn update<S: CellState>(&mut self, world: &World<S>) {
+        let (width, height) = (world.size.x as usize, world.size.y as usize);
+
+        if self.last_alive.len() != width || self.last_alive.first().map_or(0, Vec::len) != height {
+            self.last_alive = vec![vec![None; height]; width];
+        }
+
+        for (column, cells) in self.last_alive.iter_mut().zip(&world.cells) {
+            for (last_alive, cell) in column.iter_mut().zip(cells) {
+                match cell.is_alive() {
+                    true => *last_alive = Some(world.frames),
+                    // Left over from before a restart wound the frame count back.
+                    false if last_alive.is_some_and(|frame| frame > world.frames) => *last_alive = None,
+                    false => {}
+                }
+            }
+        }
+    }
+
+    // Generations since (x, y) was last alive, 0 while it still is.
+    pub fn age(&self, x: i32, y: i32, frames: u64) -> Option<u64> {
+        let last_alive = (*self.last_alive.get(x as usize)?.get(y as usize)?)?;
+
+        Some(frames.saturating_sub(last_alive))
+    }
+}
@@ -0,0 +1,84 @@
+// wasm-bindgen wrappers for driving worlds from JavaScript once the library is built for
+// wasm32-unknown-unknown. JavaScript gets a `Conway` class that owns its world, and cells come
+// back as a copied Uint8Array, so nothing on the JavaScript side points into wasm memory.
+
+use alloc::vec::Vec;
+use wasm_bindgen::prelude::*;
+
+use crate::builder::WorldBuilder;
+use crate::{CellState, Vector, World};
+
+#[wasm_bindgen]
+pub struct Conway {
+    world: World,
+}
+
+#[wasm_bindgen]
+impl Conway {
+    // A random world seeded like `--seed`, so the same seed gives the same world as the binary.
+    // Throws for a size with no cells in it.
+    #[wasm_bindgen(constructor)]
+    pub fn new(width: u32, height: u32, seed: u64, density: f64) -> Result<Conway, JsError> {
+        if width == 0 || height == 0 || width > i32::MAX as u32 || height > i32::MAX as u32 {
+            return Err(JsError::new(&alloc::format!("Invalid world size: {}x{}", width, height)));
+        }
+
+        let size = Vector { x: width as i32, y: height as i32 };
+        Ok(Conway { world: WorldBuilder::new(&size).density(density).seed(seed).build() })
+    }
+
+    pub fn tick(&mut self, generations: u32) {
+        for _ in 0..generations {
+            self.world.tick();
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn width(&self) -> u32 {
+        self.world.size.x as u32
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn height(&self) -> u32 {
+        self.world.size.y as u32
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn generation(&self) -> u64 {
+        self.world.frames
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn population(&self) -> u64 {
+        self.world.population
+    }
+
+    // Width times height bytes, row after row, 1 for alive.
+    pub fn cells(&self) -> Vec<u8> {
+        let world = &self.world;
+
+        (0..world.size.y as usize)
+            .flat_map(|y| world.cells.iter().map(move |column| column[y].state.is_live_for_counting() as u8))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cells_come_row_by_row_from_the_seeded_world() {
+        let conway = Conway::new(5, 3, 7, 0.5).unwrap_or_else(|_| panic!("5x3 is a valid size"));
+        let world = WorldBuilder::new(&Vector { x: 5, y: 3 }).density(0.5).seed(7).build();
+        let cells = conway.cells();
+
+        assert_eq!(cells.len(), 15);
+
+        for cell in world.alive_cells() {
+            assert_eq!(cells[cell.y as usize * 5 + cell.x as usize], 1);
+        }
+
+        assert_eq!(cells.iter().filter(|alive| **alive == 1).count() as u64, conway.population());
+    }
+}